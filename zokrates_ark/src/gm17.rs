@@ -85,10 +85,14 @@ impl<T: Field + ArkFieldExtensions> Backend<T, GM17> for Ark {
                 .collect(),
         };
 
-        let ark_proof = ArkProof {
-            a: serialization::to_g1::<T>(proof.proof.a),
-            b: serialization::to_g2::<T>(proof.proof.b),
-            c: serialization::to_g1::<T>(proof.proof.c),
+        // the proof comes from outside, so invalid points are rejected rather than verified
+        let ark_proof = match (
+            serialization::to_valid_g1::<T>(proof.proof.a),
+            serialization::to_valid_g2::<T>(proof.proof.b),
+            serialization::to_valid_g1::<T>(proof.proof.c),
+        ) {
+            (Some(a), Some(b), Some(c)) => ArkProof { a, b, c },
+            _ => return false,
         };
 
         let pvk: PreparedVerifyingKey<<T as ArkFieldExtensions>::ArkEngine> =
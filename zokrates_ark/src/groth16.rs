@@ -17,7 +17,7 @@ use crate::{parse_g1, parse_g2};
 use rand_0_8::{rngs::StdRng, SeedableRng};
 use zokrates_ast::ir::{ProgIterator, Statement, Witness};
 use zokrates_proof_systems::groth16::{ProofPoints, VerificationKey, G16};
-use zokrates_proof_systems::Scheme;
+use zokrates_proof_systems::{fq12_to_scrypt_string, G2Order, ProofEncoding, Scheme};
 
 fn print_type_of<T>(_: &T) {
     println!("{}", std::any::type_name::<T>())
@@ -31,52 +31,27 @@ impl<T: Field + ArkFieldExtensions> Backend<T, G16> for Ark {
         witness: Witness<T>,
         proving_key: Vec<u8>,
     ) -> Proof<T, G16> {
-        println!("{}", G16_WARNING);
-
-        let computation = Computation::with_witness(program, witness);
-
-        let inputs = computation
-            .public_inputs_values()
-            .iter()
-            .map(parse_fr::<T>)
-            .collect::<Vec<_>>();
-
-        let pk = ProvingKey::<<T as ArkFieldExtensions>::ArkEngine>::deserialize_unchecked(
-            &mut proving_key.as_slice(),
-        )
-        .unwrap();
-
-        let rng = &mut StdRng::from_entropy();
-        let proof = Groth16::<T::ArkEngine>::prove(&pk, computation, rng).unwrap();
-
-        let proof_points = ProofPoints {
-            a: parse_g1::<T>(&proof.a),
-            b: parse_g2::<T>(&proof.b),
-            c: parse_g1::<T>(&proof.c),
-        };
-
-        Proof::new(proof_points, inputs)
+        generate_proof_with_rng(program, witness, proving_key, &mut StdRng::from_entropy())
     }
 
     fn verify(vk: <G16 as Scheme<T>>::VerificationKey, proof: Proof<T, G16>) -> bool {
-        let vk = VerifyingKey {
-            alpha_g1: serialization::to_g1::<T>(vk.alpha),
-            beta_g2: serialization::to_g2::<T>(vk.beta),
-            gamma_g2: serialization::to_g2::<T>(vk.gamma),
-            delta_g2: serialization::to_g2::<T>(vk.delta),
-            gamma_abc_g1: vk
-                .gamma_abc
-                .into_iter()
-                .map(serialization::to_g1::<T>)
-                .collect(),
-        };
+        // as the solidity verifier requires, there is one input per gamma_abc point but the first
+        if proof.inputs.len() + 1 != vk.gamma_abc.len() {
+            return false;
+        }
+
+        let vk = to_ark_vk::<T>(vk);
 
         let pvk: PreparedVerifyingKey<T::ArkEngine> = prepare_verifying_key(&vk);
 
-        let ark_proof = ArkProof {
-            a: serialization::to_g1::<T>(proof.proof.a),
-            b: serialization::to_g2::<T>(proof.proof.b),
-            c: serialization::to_g1::<T>(proof.proof.c),
+        // the proof comes from outside, so invalid points are rejected rather than verified
+        let ark_proof = match (
+            serialization::to_valid_g1::<T>(proof.proof.a),
+            serialization::to_valid_g2::<T>(proof.proof.b),
+            serialization::to_valid_g1::<T>(proof.proof.c),
+        ) {
+            (Some(a), Some(b), Some(c)) => ArkProof { a, b, c },
+            _ => return false,
         };
 
         let public_inputs: Vec<_> = proof
@@ -94,24 +69,10 @@ impl<T: Field + ArkFieldExtensions> Backend<T, G16> for Ark {
 
 
     fn get_miller_beta_alpha_string(vk: <G16 as Scheme<T>>::VerificationKey) -> String {
+        let vk = to_ark_vk::<T>(vk);
 
-        let _vk = VerifyingKey {
-            alpha_g1: serialization::to_g1::<T>(vk.alpha),
-            beta_g2: serialization::to_g2::<T>(vk.beta),
-            gamma_g2: serialization::to_g2::<T>(vk.gamma),
-            delta_g2: serialization::to_g2::<T>(vk.delta),
-            gamma_abc_g1: vk
-                .gamma_abc
-                .into_iter()
-                .map(serialization::to_g1::<T>)
-                .collect(),
-        };
-
-
-        let pvk: PreparedVerifyingKey<T::ArkEngine> = prepare_verifying_key(&_vk);
-
-        let g1_prep =  <T::ArkEngine as PairingEngine>::G1Prepared::from(_vk.alpha_g1);
-        let g2_prep =  <T::ArkEngine as PairingEngine>::G2Prepared::from(_vk.beta_g2);
+        let g1_prep = <T::ArkEngine as PairingEngine>::G1Prepared::from(vk.alpha_g1);
+        let g2_prep = <T::ArkEngine as PairingEngine>::G2Prepared::from(vk.beta_g2);
 
         let alpha_g1_beta_g2 = <T::ArkEngine as PairingEngine>::miller_loop(core::iter::once(&(g1_prep, g2_prep)));
 
@@ -119,53 +80,12 @@ impl<T: Field + ArkFieldExtensions> Backend<T, G16> for Ark {
         let text = alpha_g1_beta_g2.to_string();
         //let caps: regex::Captures = re.captures(&text).unwrap();
         
-        let captures = re.captures_iter(&text);
-        let vals: Vec<_> = captures.collect();
-
-        return format!(
-            r#"{{
-                x: {{
-                    x: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }},
-                    y: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }},
-                    z: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }}
-                }},
-                y: {{
-                    x: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }},
-                    y: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }},
-                    z: {{ 
-                        x: {}n, 
-                        y: {}n 
-                    }}
-                }}
-            }}"#,
-            hex_to_decimal(vals[11].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[10].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[9].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[8].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[5].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[6].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[5].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[4].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[3].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[2].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[1].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[0].get(1).unwrap().as_str()).unwrap()
-        );
+        let limbs: Vec<String> = re
+            .captures_iter(&text)
+            .map(|c| hex_to_decimal(c.get(1).unwrap().as_str()).unwrap())
+            .collect();
+
+        fq12_to_scrypt_string(&limbs)
     }
 
 
@@ -175,28 +95,296 @@ impl<T: Field + ArkFieldExtensions> NonUniversalBackend<T, G16> for Ark {
     fn setup<I: IntoIterator<Item = Statement<T>>>(
         program: ProgIterator<T, I>,
     ) -> SetupKeypair<T, G16> {
-        println!("{}", G16_WARNING);
+        setup_with_rng(program, &mut StdRng::from_entropy())
+    }
+}
 
-        let computation = Computation::without_witness(program);
+impl Ark {
+    /// G16 setup drawing its randomness from `seed`, so that the same program and seed
+    /// always yield the same keys. Only meant for reproducible tests.
+    pub fn setup_with_seed<T: Field + ArkFieldExtensions, I: IntoIterator<Item = Statement<T>>>(
+        program: ProgIterator<T, I>,
+        seed: [u8; 32],
+    ) -> SetupKeypair<T, G16> {
+        setup_with_rng(program, &mut StdRng::from_seed(seed))
+    }
 
-        let rng = &mut StdRng::from_entropy();
-        let (pk, vk) = Groth16::<T::ArkEngine>::circuit_specific_setup(computation, rng).unwrap();
+    /// G16 proof generation drawing its randomness from `seed`, so that the same inputs
+    /// and seed always yield the same proof. Only meant for reproducible tests.
+    pub fn generate_proof_with_seed<
+        T: Field + ArkFieldExtensions,
+        I: IntoIterator<Item = Statement<T>>,
+    >(
+        program: ProgIterator<T, I>,
+        witness: Witness<T>,
+        proving_key: Vec<u8>,
+        seed: [u8; 32],
+    ) -> Proof<T, G16> {
+        generate_proof_with_rng(program, witness, proving_key, &mut StdRng::from_seed(seed))
+    }
 
-        let mut pk_vec: Vec<u8> = Vec::new();
-        pk.serialize_unchecked(&mut pk_vec).unwrap();
+    /// Checks that every point of a G16 proving key is on its curve and in the prime order
+    /// subgroup. Proof generation skips this, as it dominates the loading time of large keys,
+    /// so that a corrupted key yields garbage proofs or a panic instead of an error.
+    pub fn check_proving_key<T: Field + ArkFieldExtensions>(
+        proving_key: &[u8],
+    ) -> Result<(), String> {
+        let pk = ProvingKey::<T::ArkEngine>::deserialize_unchecked(proving_key)
+            .map_err(|e| format!("proving key failed validation: {:?}", e))?;
 
-        let vk = VerificationKey {
-            alpha: parse_g1::<T>(&vk.alpha_g1),
-            beta: parse_g2::<T>(&vk.beta_g2),
-            gamma: parse_g2::<T>(&vk.gamma_g2),
-            delta: parse_g2::<T>(&vk.delta_g2),
-            gamma_abc: vk.gamma_abc_g1.iter().map(|g1| parse_g1::<T>(g1)).collect(),
-        };
+        let g1_valid = [pk.vk.alpha_g1, pk.beta_g1, pk.delta_g1]
+            .iter()
+            .chain(&pk.vk.gamma_abc_g1)
+            .chain(&pk.a_query)
+            .chain(&pk.b_g1_query)
+            .chain(&pk.h_query)
+            .chain(&pk.l_query)
+            .all(serialization::is_valid_point);
+        let g2_valid = [pk.vk.beta_g2, pk.vk.gamma_g2, pk.vk.delta_g2]
+            .iter()
+            .chain(&pk.b_g2_query)
+            .all(serialization::is_valid_point);
+
+        if g1_valid && g2_valid {
+            Ok(())
+        } else {
+            Err("proving key failed validation: invalid curve point".to_string())
+        }
+    }
+}
 
-        SetupKeypair::new(vk, pk_vec)
+/// Converts a verification key to its ark representation
+fn to_ark_vk<T: Field + ArkFieldExtensions>(
+    vk: <G16 as Scheme<T>>::VerificationKey,
+) -> VerifyingKey<T::ArkEngine> {
+    VerifyingKey {
+        alpha_g1: serialization::to_g1::<T>(vk.alpha),
+        beta_g2: serialization::to_g2::<T>(vk.beta),
+        gamma_g2: serialization::to_g2::<T>(vk.gamma),
+        delta_g2: serialization::to_g2::<T>(vk.delta),
+        gamma_abc_g1: vk
+            .gamma_abc
+            .into_iter()
+            .map(serialization::to_g1::<T>)
+            .collect(),
     }
 }
 
+fn generate_proof_with_rng<T: Field + ArkFieldExtensions, I: IntoIterator<Item = Statement<T>>>(
+    program: ProgIterator<T, I>,
+    witness: Witness<T>,
+    proving_key: Vec<u8>,
+    rng: &mut StdRng,
+) -> Proof<T, G16> {
+    println!("{}", G16_WARNING);
+
+    let computation = Computation::with_witness(program, witness);
+
+    let inputs = computation
+        .public_inputs_values()
+        .iter()
+        .map(parse_fr::<T>)
+        .collect::<Vec<_>>();
+
+    let pk = ProvingKey::<<T as ArkFieldExtensions>::ArkEngine>::deserialize_unchecked(
+        &mut proving_key.as_slice(),
+    )
+    .unwrap();
+
+    let proof = Groth16::<T::ArkEngine>::prove(&pk, computation, rng).unwrap();
+
+    let proof_points = ProofPoints {
+        a: parse_g1::<T>(&proof.a),
+        b: parse_g2::<T>(&proof.b),
+        c: parse_g1::<T>(&proof.c),
+    };
+
+    Proof::new(proof_points, inputs)
+}
+
+fn setup_with_rng<T: Field + ArkFieldExtensions, I: IntoIterator<Item = Statement<T>>>(
+    program: ProgIterator<T, I>,
+    rng: &mut StdRng,
+) -> SetupKeypair<T, G16> {
+    println!("{}", G16_WARNING);
+
+    let computation = Computation::without_witness(program);
+
+    let (pk, vk) = Groth16::<T::ArkEngine>::circuit_specific_setup(computation, rng).unwrap();
+
+    let mut pk_vec: Vec<u8> = Vec::new();
+    pk.serialize_unchecked(&mut pk_vec).unwrap();
+
+    let vk = VerificationKey {
+        alpha: parse_g1::<T>(&vk.alpha_g1),
+        beta: parse_g2::<T>(&vk.beta_g2),
+        gamma: parse_g2::<T>(&vk.gamma_g2),
+        delta: parse_g2::<T>(&vk.delta_g2),
+        gamma_abc: vk.gamma_abc_g1.iter().map(|g1| parse_g1::<T>(g1)).collect(),
+    };
+
+    SetupKeypair::new(vk, pk_vec)
+}
+
+/// Decimal G1 point `(x, y)`
+pub type DecimalG1 = (String, String);
+
+/// Decimal G2 point, each coordinate in the component order of the exported sCrypt verifier
+pub type DecimalG2 = ((String, String), (String, String));
+
+/// The decimal values an exported BN128 sCrypt verifier passes to `pairCheckP4Precalc`
+#[derive(Debug, Clone)]
+pub struct ScryptVerifyInputs {
+    /// The 12 limbs of `millerb1a1`, in the order they appear in the verifying key literal
+    pub millerb1a1: Vec<String>,
+    pub gamma: DecimalG2,
+    pub delta: DecimalG2,
+    pub gamma_abc: Vec<DecimalG1>,
+    pub a: DecimalG1,
+    pub b: DecimalG2,
+    pub c: DecimalG1,
+    pub inputs: Vec<String>,
+    /// Component order of `b`, restored by the verifier as configured at export
+    pub proof_encoding: ProofEncoding,
+    /// Component order `gamma` and `delta` were exported in
+    pub g2_order: G2Order,
+}
+
+/// Decompresses the packed point `(x, y_odd)` of a verifying key exported with packed
+/// `gamma_abc`, as `BN256.unpackG1Point` does, so that its decimals can be passed to
+/// `scrypt_pair_check`
+pub fn scrypt_unpack_g1(x: &str, y_odd: bool) -> Result<DecimalG1, String> {
+    use num_bigint::BigUint;
+    use std::str::FromStr;
+    use zokrates_field::Bn128Field;
+
+    let x_value = BigUint::from_str(x).map_err(|_| format!("invalid coordinate: `{}`", x))?;
+    let bytes = x_value.to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(format!("invalid coordinate: `{}`", x));
+    }
+    let mut padded = vec![0u8; 32 - bytes.len()];
+    padded.extend(bytes);
+
+    let point = serialization::to_g1_compressed::<Bn128Field>(
+        zokrates_proof_systems::CompressedG1Affine(format!("0x{}", hex::encode(padded)), y_odd),
+    )?;
+    let zokrates_proof_systems::G1Affine(x, y) = parse_g1::<Bn128Field>(&point);
+    let decimal = |e: &str| {
+        hex_to_decimal(e.trim_start_matches("0x"))
+            .ok_or_else(|| format!("invalid coordinate: `{}`", e))
+    };
+    Ok((decimal(&x)?, decimal(&y)?))
+}
+
+/// Computes the result of the BN128 sCrypt verifier on `inputs` with the reference pairing,
+/// so that the same decimals can be checked against the TypeScript library
+pub fn scrypt_pair_check(inputs: &ScryptVerifyInputs) -> Result<bool, String> {
+    use ark_bn254::{Bn254, Fq, Fq12, Fq2, Fq6, Fr, G1Affine, G2Affine};
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use ark_ff::{One, PrimeField};
+    use std::str::FromStr;
+
+    fn fq(e: &str) -> Result<Fq, String> {
+        Fq::from_str(e).map_err(|_| format!("invalid coordinate: `{}`", e))
+    }
+
+    fn g1(p: &DecimalG1) -> Result<G1Affine, String> {
+        let point = G1Affine::new(fq(&p.0)?, fq(&p.1)?, false);
+        if !point.is_on_curve() {
+            return Err(format!("({}, {}) is not on the curve", p.0, p.1));
+        }
+        Ok(point)
+    }
+
+    // `createTwistPoint` swaps the components of each coordinate, we read them as ark does
+    fn g2(p: &DecimalG2) -> Result<G2Affine, String> {
+        let point = G2Affine::new(
+            Fq2::new(fq(&(p.0).0)?, fq(&(p.0).1)?),
+            Fq2::new(fq(&(p.1).0)?, fq(&(p.1).1)?),
+            false,
+        );
+        if !point.is_on_curve() {
+            return Err(format!("{:?} is not on the twist", p));
+        }
+        Ok(point)
+    }
+
+    if inputs.millerb1a1.len() != 12 {
+        return Err(format!(
+            "millerb1a1 should have 12 limbs, found {}",
+            inputs.millerb1a1.len()
+        ));
+    }
+    if inputs.gamma_abc.len() != inputs.inputs.len() + 1 {
+        return Err(format!(
+            "Expected {} inputs, found {}",
+            inputs.gamma_abc.len() - 1,
+            inputs.inputs.len()
+        ));
+    }
+
+    // the literal lists the limbs from the highest coefficient down
+    let limbs = inputs
+        .millerb1a1
+        .iter()
+        .rev()
+        .map(|l| fq(l))
+        .collect::<Result<Vec<_>, _>>()?;
+    let fq6 = |l: &[Fq]| {
+        Fq6::new(
+            Fq2::new(l[0], l[1]),
+            Fq2::new(l[2], l[3]),
+            Fq2::new(l[4], l[5]),
+        )
+    };
+    let millerb1a1 = Fq12::new(fq6(&limbs[0..6]), fq6(&limbs[6..12]));
+
+    let gamma_abc = inputs
+        .gamma_abc
+        .iter()
+        .map(g1)
+        .collect::<Result<Vec<_>, _>>()?;
+    let mut vk_x = gamma_abc[0].into_projective();
+    for (input, point) in inputs.inputs.iter().zip(&gamma_abc[1..]) {
+        let input = Fr::from_str(input).map_err(|_| format!("invalid input: `{}`", input))?;
+        vk_x += point.mul(input.into_repr());
+    }
+
+    fn swap(p: &DecimalG2) -> DecimalG2 {
+        let ((x0, x1), (y0, y1)) = p.clone();
+        ((x1, x0), (y1, y0))
+    }
+
+    let b = match inputs.proof_encoding {
+        ProofEncoding::Scrypt => inputs.b.clone(),
+        ProofEncoding::Eip197 => swap(&inputs.b),
+    };
+    let (gamma, delta) = match inputs.g2_order {
+        G2Order::Scrypt => (inputs.gamma.clone(), inputs.delta.clone()),
+        G2Order::Standard => (swap(&inputs.gamma), swap(&inputs.delta)),
+    };
+
+    let a = -g1(&inputs.a)?;
+    let pairs = [
+        (
+            <Bn254 as PairingEngine>::G1Prepared::from(a),
+            <Bn254 as PairingEngine>::G2Prepared::from(g2(&b)?),
+        ),
+        (
+            <Bn254 as PairingEngine>::G1Prepared::from(vk_x.into_affine()),
+            <Bn254 as PairingEngine>::G2Prepared::from(g2(&gamma)?),
+        ),
+        (
+            <Bn254 as PairingEngine>::G1Prepared::from(g1(&inputs.c)?),
+            <Bn254 as PairingEngine>::G2Prepared::from(g2(&delta)?),
+        ),
+    ];
+    let miller = Bn254::miller_loop(pairs.iter());
+
+    Ok(Bn254::final_exponentiation(&(miller * millerb1a1)) == Some(Fq12::one()))
+}
+
 #[cfg(test)]
 mod tests {
     use zokrates_ast::flat::{Parameter, Variable};
@@ -204,7 +392,7 @@ mod tests {
     use zokrates_interpreter::Interpreter;
 
     use super::*;
-    use zokrates_field::{Bls12_377Field, Bw6_761Field};
+    use zokrates_field::{Bls12_377Field, Bls12_381Field, Bn128Field, Bw6_761Field};
 
     #[test]
     fn verify_bls12_377_field() {
@@ -228,6 +416,120 @@ mod tests {
         assert!(ans);
     }
 
+    #[test]
+    fn verify_without_public_inputs() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::private(Variable::new(0))],
+            return_count: 0,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::new(0))],
+        };
+
+        let keypair = <Ark as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+
+        let proof =
+            <Ark as Backend<Bn128Field, G16>>::generate_proof(program, witness, keypair.pk);
+        assert!(proof.inputs.is_empty());
+
+        let mut tampered = Proof::new(proof.proof.clone(), proof.inputs.clone());
+        tampered.proof.a = proof.proof.c.clone();
+
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk.clone(), proof));
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk, tampered));
+    }
+
+    #[test]
+    fn ark_vk_conversion() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = <Ark as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let pk =
+            ProvingKey::<ark_bn254::Bn254>::deserialize_unchecked(keypair.pk.as_slice()).unwrap();
+
+        // the converted key is the one the setup generated, so it still verifies
+        assert_eq!(to_ark_vk::<Bn128Field>(keypair.vk.clone()), pk.vk);
+
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+        let proof = <Ark as Backend<Bn128Field, G16>>::generate_proof(program, witness, keypair.pk);
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn verify_with_wrong_input_count() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = <Ark as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+        let proof =
+            <Ark as Backend<Bn128Field, G16>>::generate_proof(program, witness, keypair.pk);
+
+        let mut inputs = proof.inputs.clone();
+        inputs.push(inputs[0].clone());
+        let too_many = Proof::new(proof.proof.clone(), inputs);
+        let too_few = Proof::new(proof.proof.clone(), proof.inputs[1..].to_vec());
+
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk.clone(), too_many));
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk.clone(), too_few));
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn verify_with_domain_tag() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![
+                Parameter::public(Variable::new(0)),
+                Parameter::private(Variable::new(1)),
+            ],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(1), Variable::public(0))],
+        };
+
+        let keypair = <Ark as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let tagged_vk = keypair.vk.with_domain_tag("0x2a").unwrap();
+        let interpreter = Interpreter::default();
+
+        let prove = |domain: i32| {
+            let witness = interpreter
+                .execute(
+                    program.clone(),
+                    &[Bn128Field::from(domain), Bn128Field::from(7)],
+                )
+                .unwrap();
+            let proof = <Ark as Backend<Bn128Field, G16>>::generate_proof(
+                program.clone(),
+                witness,
+                keypair.pk.clone(),
+            );
+            // the tagged key takes the public inputs following the domain
+            Proof::new(proof.proof, proof.inputs[1..].to_vec())
+        };
+
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(
+            tagged_vk.clone(),
+            prove(42)
+        ));
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(
+            tagged_vk,
+            prove(43)
+        ));
+    }
+
     #[test]
     fn verify_bw6_761_field() {
         let program: Prog<Bw6_761Field> = Prog {
@@ -249,4 +551,1149 @@ mod tests {
 
         assert!(ans);
     }
+
+    #[test]
+    fn twist_points_outside_the_subgroup_exist() {
+        // the sCrypt verifier's optional subgroup check rejects `p` unless `[r]p` is infinity
+        use ark_bn254::{Fq, Fq2, G2Affine};
+        use ark_ff::Zero;
+
+        let p = (1u64..)
+            .find_map(|x| G2Affine::get_point_from_x(Fq2::new(Fq::from(x), Fq::zero()), false))
+            .unwrap();
+
+        assert!(p.is_on_curve());
+        assert!(!p.is_in_correct_subgroup_assuming_on_curve());
+    }
+
+    #[test]
+    fn reject_invalid_proof_points() {
+        use ark_bn254::{Fq, Fq2};
+        use ark_ff::Zero;
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = <Ark as NonUniversalBackend<Bn128Field, G16>>::setup(program.clone());
+        let interpreter = Interpreter::default();
+
+        let witness = interpreter
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+
+        let proof =
+            <Ark as Backend<Bn128Field, G16>>::generate_proof(program, witness, keypair.pk);
+
+        // (1, 3) is not on y^2 = x^3 + 3
+        let mut off_curve = Proof::new(proof.proof.clone(), proof.inputs.clone());
+        off_curve.proof.c = zokrates_proof_systems::G1Affine(
+            format!("0x{:064x}", 1),
+            format!("0x{:064x}", 3),
+        );
+
+        let outside = (1u64..)
+            .filter_map(|x| {
+                ark_bn254::G2Affine::get_point_from_x(Fq2::new(Fq::from(x), Fq::zero()), false)
+            })
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        let mut outside_subgroup = Proof::new(proof.proof.clone(), proof.inputs.clone());
+        outside_subgroup.proof.b = parse_g2::<Bn128Field>(&outside);
+
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(
+            keypair.vk.clone(),
+            off_curve
+        ));
+        assert!(!<Ark as Backend<Bn128Field, G16>>::verify(
+            keypair.vk.clone(),
+            outside_subgroup
+        ));
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn check_proving_key() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = Ark::setup_with_seed(program, [6; 32]);
+        assert!(Ark::check_proving_key::<Bn128Field>(&keypair.pk).is_ok());
+
+        // moves the x coordinate of alpha off the curve
+        let mut corrupted = keypair.pk.clone();
+        corrupted[10] ^= 1;
+        assert_eq!(
+            Ark::check_proving_key::<Bn128Field>(&corrupted),
+            Err("proving key failed validation: invalid curve point".to_string())
+        );
+
+        let truncated = &keypair.pk[..keypair.pk.len() / 2];
+        assert!(Ark::check_proving_key::<Bn128Field>(truncated).is_err());
+    }
+
+    #[test]
+    fn seeded_setup_and_proof_are_reproducible() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = Ark::setup_with_seed(program.clone(), [1; 32]);
+        let other = Ark::setup_with_seed(program.clone(), [1; 32]);
+        assert_eq!(keypair.pk, other.pk);
+
+        let interpreter = Interpreter::default();
+        let witness = interpreter
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+
+        let proof = Ark::generate_proof_with_seed(
+            program.clone(),
+            witness.clone(),
+            keypair.pk.clone(),
+            [2; 32],
+        );
+        let other = Ark::generate_proof_with_seed(
+            program.clone(),
+            witness.clone(),
+            keypair.pk.clone(),
+            [2; 32],
+        );
+        assert_eq!(
+            format!("{:?}", proof.proof.a),
+            format!("{:?}", other.proof.a)
+        );
+        assert_eq!(
+            format!("{:?}", proof.proof.c),
+            format!("{:?}", other.proof.c)
+        );
+
+        let different = Ark::generate_proof_with_seed(program, witness, keypair.pk.clone(), [3; 32]);
+        assert_ne!(
+            format!("{:?}", proof.proof.a),
+            format!("{:?}", different.proof.a)
+        );
+
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(keypair.vk, proof));
+    }
+
+    #[test]
+    fn miller_beta_alpha_limbs() {
+        use ark_ff::{BigInteger, PrimeField};
+        use num_bigint::BigUint;
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program, [7; 32]);
+
+        let alpha = serialization::to_g1::<Bn128Field>(keypair.vk.alpha.clone());
+        let beta = serialization::to_g2::<Bn128Field>(keypair.vk.beta.clone());
+        let g1_prep = <ark_bn254::Bn254 as PairingEngine>::G1Prepared::from(alpha);
+        let g2_prep = <ark_bn254::Bn254 as PairingEngine>::G2Prepared::from(beta);
+        let miller = ark_bn254::Bn254::miller_loop(core::iter::once(&(g1_prep, g2_prep)));
+        let limb =
+            |e: &ark_bn254::Fq| BigUint::from_bytes_le(&e.into_repr().to_bytes_le()).to_string();
+        // the literal lists the limbs from `c1.c2.c1` down to `c0.c0.c0`
+        let expected: Vec<String> = [miller.c0, miller.c1]
+            .iter()
+            .flat_map(|c| [c.c0, c.c1, c.c2])
+            .flat_map(|c| [limb(&c.c0), limb(&c.c1)])
+            .rev()
+            .collect();
+
+        let literal = <Ark as Backend<Bn128Field, G16>>::get_miller_beta_alpha_string(keypair.vk);
+        let limbs: Vec<String> = Regex::new(r"(\d+)n")
+            .unwrap()
+            .captures_iter(&literal)
+            .map(|c| c[1].to_string())
+            .collect();
+
+        assert_eq!(limbs, expected);
+    }
+
+    fn decimal(e: &str) -> String {
+        hex_to_decimal(e.trim_start_matches("0x")).unwrap()
+    }
+
+    fn decimal_g1(p: &zokrates_proof_systems::G1Affine) -> DecimalG1 {
+        (decimal(&p.0), decimal(&p.1))
+    }
+
+    fn decimal_g2(p: &zokrates_proof_systems::G2Affine) -> DecimalG2 {
+        match p {
+            zokrates_proof_systems::G2Affine::Fq2(p) => (
+                (decimal(&(p.0).0), decimal(&(p.0).1)),
+                (decimal(&(p.1).0), decimal(&(p.1).1)),
+            ),
+            _ => unreachable!(),
+        }
+    }
+
+    /// The decimals an exported BN128 sCrypt verifier of `vk` is called with on `proof`
+    fn scrypt_verify_inputs(
+        vk: &<G16 as Scheme<Bn128Field>>::VerificationKey,
+        proof: &Proof<Bn128Field, G16>,
+    ) -> ScryptVerifyInputs {
+        use ark_ff::{BigInteger, PrimeField};
+        use num_bigint::BigUint;
+
+        let alpha = serialization::to_g1::<Bn128Field>(vk.alpha.clone());
+        let beta = serialization::to_g2::<Bn128Field>(vk.beta.clone());
+        let g1_prep = <ark_bn254::Bn254 as PairingEngine>::G1Prepared::from(alpha);
+        let g2_prep = <ark_bn254::Bn254 as PairingEngine>::G2Prepared::from(beta);
+        let miller = ark_bn254::Bn254::miller_loop(core::iter::once(&(g1_prep, g2_prep)));
+        let limb =
+            |e: &ark_bn254::Fq| BigUint::from_bytes_le(&e.into_repr().to_bytes_le()).to_string();
+        let millerb1a1 = [miller.c0, miller.c1]
+            .iter()
+            .flat_map(|c| [c.c0, c.c1, c.c2])
+            .flat_map(|c| [limb(&c.c0), limb(&c.c1)])
+            .rev()
+            .collect();
+
+        ScryptVerifyInputs {
+            millerb1a1,
+            gamma: decimal_g2(&vk.gamma),
+            delta: decimal_g2(&vk.delta),
+            gamma_abc: vk.gamma_abc.iter().map(decimal_g1).collect(),
+            a: decimal_g1(&proof.proof.a),
+            b: decimal_g2(&proof.proof.b),
+            c: decimal_g1(&proof.proof.c),
+            inputs: proof.inputs.iter().map(|i| decimal(i)).collect(),
+            proof_encoding: ProofEncoding::Scrypt,
+            g2_order: G2Order::Scrypt,
+        }
+    }
+
+    #[test]
+    fn scrypt_pair_check_matches_verifier() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![
+                Parameter::public(Variable::new(0)),
+                Parameter::private(Variable::new(1)),
+            ],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(1), Variable::public(0))],
+        };
+
+        let keypair = Ark::setup_with_seed(program.clone(), [4; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(3), Bn128Field::from(7)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [5; 32]);
+
+        let inputs = scrypt_verify_inputs(&keypair.vk, &proof);
+        assert_eq(inputs.inputs, vec!["3".to_string(), "7".to_string()]);
+        assert!(scrypt_pair_check(&inputs).unwrap());
+
+        // a key exported with packed `gamma_abc` gives back the same points once unpacked
+        let unpacked = keypair
+            .vk
+            .gamma_abc
+            .iter()
+            .map(|p| {
+                let point = serialization::to_g1::<Bn128Field>(p.clone());
+                let zokrates_proof_systems::CompressedG1Affine(x, y_odd) =
+                    crate::parse_g1_compressed::<Bn128Field>(&point);
+                scrypt_unpack_g1(&decimal(&x), y_odd).unwrap()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(unpacked, inputs.gamma_abc);
+        assert!(scrypt_unpack_g1("not a number", false).is_err());
+
+        let mut tampered = inputs.clone();
+        tampered.inputs[0] = "4".into();
+        assert!(!scrypt_pair_check(&tampered).unwrap());
+
+        let mut tampered = inputs.clone();
+        tampered.millerb1a1.swap(4, 5);
+        assert!(!scrypt_pair_check(&tampered).unwrap());
+
+        // the same key with `gamma` and `delta` in the standard order, imaginary component first
+        let standard =
+            |p: &zokrates_proof_systems::G2Affine| decimal_g2(&p.with_order(G2Order::Standard));
+        let mut reordered = inputs.clone();
+        reordered.gamma = standard(&keypair.vk.gamma);
+        reordered.delta = standard(&keypair.vk.delta);
+        assert!(!scrypt_pair_check(&reordered).unwrap_or(false));
+
+        reordered.g2_order = G2Order::Standard;
+        assert!(scrypt_pair_check(&reordered).unwrap());
+
+        // the same proof with `b` in EIP-197 order, imaginary component first
+        let ((x0, x1), (y0, y1)) = inputs.b.clone();
+        let mut eip197 = inputs;
+        eip197.b = ((x1, x0), (y1, y0));
+        assert!(!scrypt_pair_check(&eip197).unwrap_or(false));
+
+        eip197.proof_encoding = ProofEncoding::Eip197;
+        assert!(scrypt_pair_check(&eip197).unwrap());
+    }
+
+    #[test]
+    fn scrypt_pair_check_rejects_untagged_proofs() {
+        // the first public input is the domain tag
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![
+                Parameter::public(Variable::new(0)),
+                Parameter::private(Variable::new(1)),
+            ],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(1), Variable::public(0))],
+        };
+
+        let keypair = Ark::setup_with_seed(program.clone(), [6; 32]);
+        let prove = |tag: u32, seed: [u8; 32]| {
+            let witness = Interpreter::default()
+                .execute(
+                    program.clone(),
+                    &[Bn128Field::from(tag), Bn128Field::from(7)],
+                )
+                .unwrap();
+            Ark::generate_proof_with_seed(program.clone(), witness, keypair.pk.clone(), seed)
+        };
+        let tagged_vk = keypair.vk.with_domain_tag("0x3").unwrap();
+
+        // the tag is fixed by the key, the verifier only takes the remaining inputs
+        let tagged = prove(3, [7; 32]);
+        let mut inputs = scrypt_verify_inputs(&tagged_vk, &tagged);
+        assert_eq!(inputs.inputs.remove(0), "3");
+        assert!(scrypt_pair_check(&inputs).unwrap());
+
+        // a proof for another tag verifies against the untagged key only
+        let untagged = prove(4, [8; 32]);
+        assert!(scrypt_pair_check(&scrypt_verify_inputs(&keypair.vk, &untagged)).unwrap());
+
+        let mut inputs = scrypt_verify_inputs(&tagged_vk, &untagged);
+        assert_eq!(inputs.inputs.remove(0), "4");
+        assert!(!scrypt_pair_check(&inputs).unwrap());
+    }
+
+    /// Reads the bigint constant `name` of the `BN256` class of the sCrypt pairing library
+    fn scrypt_bn256_constant(name: &str) -> num_bigint::BigUint {
+        let lib = zokrates_proof_systems::scrypt_pairing_lib_bn128();
+        let constant = Regex::new(&format!(r"static readonly {}: bigint =\s*(\d+)n", name))
+            .unwrap()
+            .captures(lib)
+            .unwrap()[1]
+            .to_string();
+        constant.parse().unwrap()
+    }
+
+    /// Multiplies `a` by `m` as `BN256.mulTwistPoint` does: over the `CURVE_BITS_P8` bits of
+    /// `m` from the highest down, doubling the sum once a set bit was met and adding `a` on
+    /// each set bit
+    fn scrypt_mul_twist_point(
+        a: &ark_bn254::G2Affine,
+        m: &num_bigint::BigUint,
+    ) -> ark_bn254::G2Projective {
+        use ark_ec::{AffineCurve, ProjectiveCurve};
+        use ark_ff::Zero;
+
+        let bits: u64 = scrypt_bn256_constant("CURVE_BITS_P8").try_into().unwrap();
+        let mut sum = ark_bn254::G2Projective::zero();
+        let mut t = ark_bn254::G2Projective::zero();
+        let mut first_one = false;
+        for i in (0..bits).rev() {
+            if first_one {
+                t = sum.double();
+            }
+            if m.bit(i) {
+                first_one = true;
+                sum = t + a.into_projective();
+            } else {
+                sum = t;
+            }
+        }
+        sum
+    }
+
+    #[test]
+    fn scrypt_subgroup_check_rejects_twist_points_outside_g2() {
+        use ark_bn254::{Fq, Fq2, G2Affine};
+        use ark_ec::AffineCurve;
+        use ark_ff::{One, Zero};
+        use zokrates_common::helpers::CurveParameter;
+        use zokrates_proof_systems::{ScryptCompatibleScheme, ScryptExportConfig};
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::private(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [9; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(5)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [10; 32]);
+
+        // the check the verifier emits for `b`, whose multiple by the group order must vanish
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            keypair.vk,
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().subgroup_checks(true),
+        )
+        .unwrap();
+        assert!(verifier.contains(
+            "BN256.isInfTwistPoint(BN256.mulTwistPoint(BN256.createTwistPoint(proof.b), BN256.R))"
+        ));
+        let r = scrypt_bn256_constant("R");
+
+        let b = serialization::to_g2::<Bn128Field>(proof.proof.b);
+        assert!(scrypt_mul_twist_point(&b, &r).is_zero());
+
+        // a point on the twist outside G2, whose order is a multiple of the cofactor
+        let outside = (1u64..)
+            .filter_map(|i| G2Affine::get_point_from_x(Fq2::new(Fq::from(i), Fq::one()), false))
+            .find(|p| !p.is_in_correct_subgroup_assuming_on_curve())
+            .unwrap();
+        assert!(outside.is_on_curve());
+        assert!(!scrypt_mul_twist_point(&outside, &r).is_zero());
+
+        // the mirror multiplies as ark does
+        let three = num_bigint::BigUint::from(3u32);
+        assert_eq!(scrypt_mul_twist_point(&outside, &three), outside.mul(3u64));
+    }
+
+    /// Reorders the components of `b` as the verifier does before its pair check, following
+    /// the `const b: G2Point` it emits to restore an EIP-197 encoded `proof.b`
+    fn scrypt_restore_b(verifier: &str, b: &DecimalG2) -> DecimalG2 {
+        let restore = Regex::new(
+            r"const b: G2Point = \{\s*x: \{ x: proof\.b\.(\w)\.(\w), y: proof\.b\.(\w)\.(\w) \},\s*y: \{ x: proof\.b\.(\w)\.(\w), y: proof\.b\.(\w)\.(\w) \},\s*\}",
+        )
+        .unwrap();
+        let component = |coordinate: &str, c: &str| {
+            let coordinate = match coordinate {
+                "x" => &b.0,
+                _ => &b.1,
+            };
+            match c {
+                "x" => coordinate.0.clone(),
+                _ => coordinate.1.clone(),
+            }
+        };
+        match restore.captures(verifier) {
+            Some(c) => (
+                (component(&c[1], &c[2]), component(&c[3], &c[4])),
+                (component(&c[5], &c[6]), component(&c[7], &c[8])),
+            ),
+            None => b.clone(),
+        }
+    }
+
+    #[test]
+    fn scrypt_verifier_restores_eip197_proofs() {
+        use zokrates_common::helpers::CurveParameter;
+        use zokrates_proof_systems::{ScryptCompatibleScheme, ScryptExportConfig};
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [11; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(8)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [12; 32]);
+
+        let export = |encoding: ProofEncoding| {
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                keypair.vk.clone(),
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default().proof_encoding(encoding),
+            )
+            .unwrap()
+        };
+        let scrypt = export(ProofEncoding::Scrypt);
+        let eip197 = export(ProofEncoding::Eip197);
+
+        // the proof with `b` re-encoded in EIP-197 order, imaginary component first
+        let inputs = scrypt_verify_inputs(&keypair.vk, &proof);
+        let ((x0, x1), (y0, y1)) = inputs.b.clone();
+        let eip197_b = ((x1, x0), (y1, y0));
+
+        let mut restored = inputs.clone();
+        restored.b = scrypt_restore_b(&eip197, &eip197_b);
+        assert_eq!(restored.b, inputs.b);
+        assert!(scrypt_pair_check(&restored).unwrap());
+
+        // a verifier exported for scrypt encoded proofs takes `b` as it is
+        let mut unrestored = inputs.clone();
+        unrestored.b = scrypt_restore_b(&scrypt, &eip197_b);
+        assert_eq!(unrestored.b, eip197_b);
+        assert!(!scrypt_pair_check(&unrestored).unwrap_or(false));
+
+        assert_eq!(scrypt_restore_b(&scrypt, &inputs.b), inputs.b);
+        assert!(scrypt_pair_check(&inputs).unwrap());
+    }
+
+    const EXP_FQ12_U: &str = "    static expFQ12_u(a: FQ12): FQ12 {\n";
+    const FINAL_EXPONENTIATION: &str = "    static finalExponentiation(a: FQ12): FQ12 {\n";
+
+    /// Evaluates the static method `signature` of the BN128 sCrypt pairing library on `a`,
+    /// following its unrolled schedule statement by statement with ark arithmetic
+    fn run_scrypt_schedule(signature: &str, a: ark_bn254::Fq12) -> ark_bn254::Fq12 {
+        use ark_ff::{Field as _, One};
+        use std::collections::HashMap;
+
+        let lib = zokrates_proof_systems::scrypt_pairing_lib_bn128();
+        let start = lib.find(signature).unwrap() + signature.len();
+        let body = &lib[start..start + lib[start..].find("\n    }\n").unwrap()];
+
+        // one statement per line, but for the struct literal opening `finalExponentiation`
+        let mut statements = vec![];
+        let mut current = String::new();
+        for line in body
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("//"))
+        {
+            current = format!("{} {}", current, line).trim().to_string();
+            if current.matches('{').count() == current.matches('}').count() {
+                statements.push(std::mem::take(&mut current));
+            }
+        }
+
+        let assignment = Regex::new(r"^(?:let |const )?(\w+)(?:: FQ12)? = (.*)$").unwrap();
+        let conjugate = Regex::new(r"^\{ x: BN256\.negFQ6\((\w+)\.x\), y: (\w+)\.y, \}$").unwrap();
+        let call = Regex::new(r"^BN256\.(\w+)\((\w+)(?:, (\w+))?\)$").unwrap();
+
+        let mut values = HashMap::new();
+        values.insert("a".to_string(), a);
+        for statement in statements {
+            if let Some(name) = statement.strip_prefix("return ") {
+                return values[name];
+            }
+
+            let assigned = assignment
+                .captures(&statement)
+                .unwrap_or_else(|| panic!("unexpected statement `{}`", statement));
+            let value = if &assigned[2] == "BN256.FQ12One" {
+                ark_bn254::Fq12::one()
+            } else if let Some(c) = conjugate.captures(&assigned[2]) {
+                assert_eq!(&c[1], &c[2]);
+                let mut x = values[&c[1]];
+                x.conjugate();
+                x
+            } else {
+                let c = call
+                    .captures(&assigned[2])
+                    .unwrap_or_else(|| panic!("unexpected statement `{}`", statement));
+                let mut x = values[&c[2]];
+                match &c[1] {
+                    "squareFQ12" => x.square(),
+                    "mulFQ12" => x * values[&c[3]],
+                    "modFQ12" => x,
+                    "inverseFQ12" => x.inverse().unwrap(),
+                    "conjugateFQ12" => {
+                        x.conjugate();
+                        x
+                    }
+                    "frobeniusFQ12" => {
+                        x.frobenius_map(1);
+                        x
+                    }
+                    "frobeniusP2FQ12" => {
+                        x.frobenius_map(2);
+                        x
+                    }
+                    "expFQ12_u" => run_scrypt_schedule(EXP_FQ12_U, x),
+                    f => panic!("unexpected method `{}`", f),
+                }
+            };
+            values.insert(assigned[1].to_string(), value);
+        }
+
+        panic!("`{}` has no return statement", signature.trim())
+    }
+
+    const MILLER: &str = "    static miller(q: TwistPoint, p: CurvePoint): FQ12 {\n";
+    const MILLER_FUSED_3: &str = "    static millerFused3(q_0: TwistPoint, p_0: CurvePoint, q_1: TwistPoint, p_1: CurvePoint, q_2: TwistPoint, p_2: CurvePoint): FQ12 {\n";
+
+    /// Evaluates the Miller loop `signature` of the BN128 sCrypt pairing library on the points
+    /// named by `arguments`. Points and line functions are kept as symbolic terms, and
+    /// multiplying the accumulator by a line function multiplies it by a random value drawn
+    /// once per term in `lines`, so two loops agree if they multiply in the same line functions
+    /// of the same points between the same squarings
+    fn run_scrypt_miller(
+        signature: &str,
+        arguments: &[(&str, &str)],
+        lines: &mut std::collections::HashMap<String, ark_bn254::Fq12>,
+        rng: &mut StdRng,
+    ) -> ark_bn254::Fq12 {
+        use ark_ff::{Field as _, One, UniformRand};
+        use std::collections::HashMap;
+
+        let lib = zokrates_proof_systems::scrypt_pairing_lib_bn128();
+        let start = lib.find(signature).unwrap() + signature.len();
+        let body = &lib[start..start + lib[start..].find("\n    }\n").unwrap()];
+
+        // statements span several lines until their brackets are balanced
+        let mut statements = vec![];
+        let mut current = String::new();
+        for line in body
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with("//"))
+        {
+            current = format!("{} {}", current, line).trim().to_string();
+            if current.matches(|c| c == '(' || c == '{').count()
+                == current.matches(|c| c == ')' || c == '}').count()
+            {
+                statements.push(std::mem::take(&mut current));
+            }
+        }
+
+        let assignment = Regex::new(r"^(?:let |const )?(\w+)(?:: \w+)? = (.*)$").unwrap();
+        let accumulator = Regex::new(r"\bret\b").unwrap();
+        let identifier = Regex::new(r"\.?\b[A-Za-z_]\w*\b").unwrap();
+        let reduction = Regex::new(r"^BN256\.(modFQ12|squareFQ12)\((.*)\)$").unwrap();
+        let mul_line =
+            Regex::new(r"^BN256Pairing\.mulLine\((.*), (\w+)\.a, (\w+)\.b, (\w+)\.c\)$").unwrap();
+
+        let mut terms: HashMap<String, String> = arguments
+            .iter()
+            .map(|(name, term)| (name.to_string(), term.to_string()))
+            .collect();
+        let mut ret = ark_bn254::Fq12::one();
+
+        fn evaluate(
+            expression: &str,
+            ret: ark_bn254::Fq12,
+            terms: &HashMap<String, String>,
+            lines: &mut HashMap<String, ark_bn254::Fq12>,
+            rng: &mut StdRng,
+            reduction: &Regex,
+            mul_line: &Regex,
+        ) -> ark_bn254::Fq12 {
+            if expression == "ret" {
+                ret
+            } else if expression == "BN256.FQ12One" {
+                ark_bn254::Fq12::one()
+            } else if let Some(c) = reduction.captures(expression) {
+                let x = evaluate(&c[2], ret, terms, lines, rng, reduction, mul_line);
+                match &c[1] {
+                    "squareFQ12" => x.square(),
+                    _ => x,
+                }
+            } else if let Some(c) = mul_line.captures(expression) {
+                assert!(c[2] == c[3] && c[3] == c[4]);
+                let x = evaluate(&c[1], ret, terms, lines, rng, reduction, mul_line);
+                let line = *lines
+                    .entry(terms[&c[2]].clone())
+                    .or_insert_with(|| ark_bn254::Fq12::rand(rng));
+                x * line
+            } else {
+                panic!("unexpected expression `{}`", expression)
+            }
+        }
+
+        for statement in statements {
+            if let Some(expression) = statement.strip_prefix("return ") {
+                return evaluate(expression, ret, &terms, lines, rng, &reduction, &mul_line);
+            }
+
+            let assigned = assignment
+                .captures(&statement)
+                .unwrap_or_else(|| panic!("unexpected statement `{}`", statement));
+            if accumulator.is_match(&statement) {
+                assert_eq!(&assigned[1], "ret");
+                ret = evaluate(&assigned[2], ret, &terms, lines, rng, &reduction, &mul_line);
+            } else {
+                // replace the variables by their terms, but not the fields of a value
+                let term = identifier
+                    .replace_all(&assigned[2], |c: &regex::Captures| match terms.get(&c[0]) {
+                        Some(term) => format!("({})", term),
+                        None => c[0].to_string(),
+                    })
+                    .to_string();
+                terms.insert(assigned[1].to_string(), term);
+            }
+        }
+
+        panic!("`{}` has no return statement", signature.trim())
+    }
+
+    #[test]
+    fn scrypt_fused_miller_matches_separate_loops() {
+        use ark_ff::One;
+        use std::collections::HashMap;
+
+        let mut lines = HashMap::new();
+        let mut rng = StdRng::from_seed([9; 32]);
+        let mut separate = |pairs: [(&str, &str); 3]| {
+            pairs.iter().fold(ark_bn254::Fq12::one(), |acc, &(q, p)| {
+                acc * run_scrypt_miller(MILLER, &[("q", q), ("p", p)], &mut lines, &mut rng)
+            })
+        };
+        let expected = separate([("Q0", "P0"), ("Q1", "P1"), ("Q2", "P2")]);
+        let mismatched = separate([("Q0", "P1"), ("Q1", "P0"), ("Q2", "P2")]);
+
+        let fused = run_scrypt_miller(
+            MILLER_FUSED_3,
+            &[
+                ("q_0", "Q0"),
+                ("p_0", "P0"),
+                ("q_1", "Q1"),
+                ("p_1", "P1"),
+                ("q_2", "Q2"),
+                ("p_2", "P2"),
+            ],
+            &mut lines,
+            &mut rng,
+        );
+        if let Some((i, expected, actual)) = diverging_limb(&expected, &fused) {
+            panic!(
+                "millerFused3 diverges at limb {}: {} != {}",
+                i, expected, actual
+            );
+        }
+        assert_ne!(fused, mismatched);
+    }
+
+    /// The first limb, in `c0.c0.c0` first order, at which `a` and `b` differ, with their values
+    fn diverging_limb(a: &ark_bn254::Fq12, b: &ark_bn254::Fq12) -> Option<(usize, String, String)> {
+        let limbs = |e: &ark_bn254::Fq12| -> Vec<String> {
+            [e.c0, e.c1]
+                .iter()
+                .flat_map(|c| [c.c0, c.c1, c.c2])
+                .flat_map(|c| [c.c0.to_string(), c.c1.to_string()])
+                .collect()
+        };
+        limbs(a)
+            .into_iter()
+            .zip(limbs(b))
+            .enumerate()
+            .find(|(_, (a, b))| a != b)
+            .map(|(i, (a, b))| (i, a, b))
+    }
+
+    #[test]
+    fn scrypt_final_exponentiation_matches_ark() {
+        use ark_ff::{Field as _, UniformRand};
+        use num_bigint::BigUint;
+
+        let modulus = |limbs: &[u64]| {
+            limbs
+                .iter()
+                .rev()
+                .fold(BigUint::from(0u32), |acc, l| (acc << 64usize) + *l)
+        };
+        let p = modulus(<ark_bn254::Fq as ark_ff::Field>::characteristic());
+        let r = modulus(<ark_bn254::Fr as ark_ff::Field>::characteristic());
+        let final_exponent = (p.pow(12u32) - 1u32) / r;
+        let u = 4965661367192848881u64;
+
+        let mut rng = StdRng::from_seed([8; 32]);
+        for _ in 0..2 {
+            let a = ark_bn254::Fq12::rand(&mut rng);
+
+            let expected = a.pow([u]);
+            let actual = run_scrypt_schedule(EXP_FQ12_U, a);
+            if let Some((i, expected, actual)) = diverging_limb(&expected, &actual) {
+                panic!(
+                    "expFQ12_u diverges at limb {}: {} != {}",
+                    i, expected, actual
+                );
+            }
+
+            let expected = a.pow(final_exponent.to_u64_digits());
+            let actual = run_scrypt_schedule(FINAL_EXPONENTIATION, a);
+            if let Some((i, expected, actual)) = diverging_limb(&expected, &actual) {
+                panic!(
+                    "finalExponentiation diverges at limb {}: {} != {}",
+                    i, expected, actual
+                );
+            }
+        }
+    }
+
+    /// Evaluates the check `scrypt_pair_check_bn128` emits for `pairs` multiplied by `precalc`,
+    /// statement by statement, with ark Miller loops standing for `BN256Pairing.miller` and the
+    /// schedule of the library for the final exponentiation
+    fn run_scrypt_pair_check(
+        precalc: ark_bn254::Fq12,
+        pairs: &[(ark_bn254::G1Affine, ark_bn254::G2Affine)],
+    ) -> bool {
+        use ark_ff::{One, Zero};
+
+        // `millerAccumulate` multiplies in the pairs without a point at infinity
+        assert!(zokrates_proof_systems::scrypt_pairing_lib_bn128().contains(
+            "if (!BN256.isInfCurvePoint(p) && !BN256.isInfTwistPoint(q)) {
+            acc = BN256.mulFQ12(acc, BN256Pairing.miller(q, p))"
+        ));
+
+        let names: Vec<_> = (0..pairs.len())
+            .map(|i| (format!("a{}", i), format!("b{}", i)))
+            .collect();
+        let names: Vec<_> = names
+            .iter()
+            .map(|(a, b)| (a.as_str(), b.as_str()))
+            .collect();
+        let check = zokrates_proof_systems::scrypt_pair_check_bn128("precalc", &names, "");
+
+        let accumulate =
+            Regex::new(r"^acc = BN256Pairing\.millerAccumulate\(acc, a(\d+), b(\d+)\)$").unwrap();
+        let mut acc = None;
+        for statement in check.lines().map(str::trim) {
+            if statement == "let acc = precalc" {
+                acc = Some(precalc);
+            } else if let Some(c) = accumulate.captures(statement) {
+                assert_eq!(&c[1], &c[2]);
+                let (a, b) = pairs[c[1].parse::<usize>().unwrap()];
+                if !a.is_zero() && !b.is_zero() {
+                    let pair = (
+                        <ark_bn254::Bn254 as PairingEngine>::G1Prepared::from(a),
+                        <ark_bn254::Bn254 as PairingEngine>::G2Prepared::from(b),
+                    );
+                    acc =
+                        acc.map(|acc| acc * ark_bn254::Bn254::miller_loop(core::iter::once(&pair)));
+                }
+            } else if statement == "return BN256Pairing.pairCheckFinal(acc)" {
+                return run_scrypt_schedule(FINAL_EXPONENTIATION, acc.unwrap())
+                    == ark_bn254::Fq12::one();
+            } else {
+                panic!("unexpected statement `{}`", statement);
+            }
+        }
+
+        panic!("the check has no return statement")
+    }
+
+    #[test]
+    fn scrypt_accumulated_pair_check_matches_verifier() {
+        use ark_bn254::{Bn254, Fr};
+        use ark_ec::{AffineCurve, ProjectiveCurve};
+        use ark_ff::{One, PrimeField, Zero};
+
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [13; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(8)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [14; 32]);
+        assert_eq!(proof.inputs.len(), 2);
+
+        let vk = to_ark_vk::<Bn128Field>(keypair.vk);
+        let a = serialization::to_g1::<Bn128Field>(proof.proof.a);
+        let b = serialization::to_g2::<Bn128Field>(proof.proof.b);
+        let c = serialization::to_g1::<Bn128Field>(proof.proof.c);
+        // both public inputs are 8
+        let vk_x = vk
+            .gamma_abc_g1
+            .iter()
+            .skip(1)
+            .fold(vk.gamma_abc_g1[0].into_projective(), |acc, g| {
+                acc + g.mul(Fr::from(8u64).into_repr())
+            })
+            .into_affine();
+        let miller = |p: ark_bn254::G1Affine, q: ark_bn254::G2Affine| {
+            let pair = (
+                <Bn254 as PairingEngine>::G1Prepared::from(p),
+                <Bn254 as PairingEngine>::G2Prepared::from(q),
+            );
+            Bn254::miller_loop(core::iter::once(&pair))
+        };
+
+        // four pairs, none of them precomputed
+        let pairs = [
+            (-a, b),
+            (vk.alpha_g1, vk.beta_g2),
+            (vk_x, vk.gamma_g2),
+            (c, vk.delta_g2),
+        ];
+        assert!(run_scrypt_pair_check(ark_bn254::Fq12::one(), &pairs));
+
+        // two pairs after the precomputed ones, as a caller with a fixed vk_x would
+        let precalc = miller(vk.alpha_g1, vk.beta_g2) * miller(vk_x, vk.gamma_g2);
+        assert!(run_scrypt_pair_check(precalc, &[(-a, b), (c, vk.delta_g2)]));
+        assert!(!run_scrypt_pair_check(
+            precalc,
+            &[(-c, b), (a, vk.delta_g2)]
+        ));
+
+        // a pair at infinity is skipped, any other tampering is caught
+        let mut with_infinity = pairs.to_vec();
+        with_infinity.push((ark_bn254::G1Affine::zero(), vk.gamma_g2));
+        assert!(run_scrypt_pair_check(
+            ark_bn254::Fq12::one(),
+            &with_infinity
+        ));
+
+        let mut tampered = with_infinity;
+        tampered[4].0 = a;
+        assert!(!run_scrypt_pair_check(ark_bn254::Fq12::one(), &tampered));
+    }
+
+    /// The points of the verifying key literal of an exported BLS12-381 verifier, `alpha`,
+    /// `beta`, `gamma`, `delta` and then `gammaAbc`, each as its list of coordinates
+    fn bls12_381_scrypt_vk(verifying_key_data: &str) -> Vec<Vec<num_bigint::BigUint>> {
+        let number = Regex::new(r"0x[0-9a-fA-F]+|\b0\b").unwrap();
+        let numbers = |text: &str| {
+            number
+                .find_iter(text)
+                .map(|n| {
+                    let digits = n.as_str().trim_start_matches("0x");
+                    num_bigint::BigUint::parse_bytes(digits.as_bytes(), 16).unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let mut points: Vec<_> = ["alpha", "beta", "gamma", "delta"]
+            .iter()
+            .map(|name| {
+                let field = Regex::new(&format!(r"\b{}: (.*),\n", name)).unwrap();
+                numbers(&field.captures(verifying_key_data).unwrap()[1])
+            })
+            .collect();
+        let gamma_abc = Regex::new(r"(?s)gammaAbc: \[(.*?)\n\s*\]\n").unwrap();
+        let gamma_abc = &gamma_abc.captures(verifying_key_data).unwrap()[1];
+        points.extend(gamma_abc.split("],").map(numbers));
+        points
+    }
+
+    /// Verifies `proof` with the reference pairing against the points of an exported
+    /// BLS12-381 verifying key, read back from Montgomery form with the components of each
+    /// `fe2` taken as `[c0, c1]`, or the other way around if `swap_fe2`. The proof goes
+    /// through the same conversion as `ZKSNARK.toPointG1` and `ZKSNARK.toPointG2` apply.
+    fn bls12_381_scrypt_verify(
+        points: &[Vec<num_bigint::BigUint>],
+        proof: &Proof<Bls12_381Field, G16>,
+        swap_fe2: bool,
+    ) -> bool {
+        use ark_ec::{AffineCurve, ProjectiveCurve};
+        use ark_ff::{One, PrimeField, Zero};
+        use num_bigint::BigUint;
+        use zokrates_proof_systems::{G1Affine, G2Affine, G2AffineFq2};
+
+        type E = <Bls12_381Field as ArkFieldExtensions>::ArkEngine;
+
+        let p = <<E as PairingEngine>::Fq as ark_ff::Field>::characteristic()
+            .iter()
+            .rev()
+            .fold(BigUint::from(0u32), |acc, l| (acc << 64usize) + *l);
+        let zero = BigUint::from(0u32);
+        let r1 = (BigUint::from(1u32) << 384usize) % &p;
+        let r_inv = r1.modpow(&(&p - 2u32), &p);
+        let from_mont = |m: &BigUint| m * &r_inv % &p;
+        let to_mont = |h: &str| {
+            let n = BigUint::parse_bytes(h.trim_start_matches("0x").as_bytes(), 16).unwrap();
+            (n << 384usize) % &p
+        };
+        let hex = |m: &BigUint| format!("0x{:0>96}", from_mont(m).to_str_radix(16));
+
+        // `[x, y, z]` with `z` the Montgomery form of one, or all zero at infinity
+        let g1 = |c: &[BigUint]| -> Option<<E as PairingEngine>::G1Affine> {
+            if c.iter().all(|n| *n == zero) {
+                return Some(Zero::zero());
+            }
+            if c[2] != r1 {
+                return None;
+            }
+            serialization::to_valid_g1::<Bls12_381Field>(G1Affine(hex(&c[0]), hex(&c[1])))
+        };
+        // `[[x0, x1], [y0, y1], [z0, z1]]` with `z` the Montgomery form of one
+        let g2 = |c: &[BigUint]| -> Option<<E as PairingEngine>::G2Affine> {
+            if c.iter().all(|n| *n == zero) {
+                return Some(Zero::zero());
+            }
+            if c[4] != r1 || c[5] != zero {
+                return None;
+            }
+            let fe2 = |c0: &BigUint, c1: &BigUint| match swap_fe2 {
+                false => (hex(c0), hex(c1)),
+                true => (hex(c1), hex(c0)),
+            };
+            serialization::to_valid_g2::<Bls12_381Field>(G2Affine::Fq2(G2AffineFq2(
+                fe2(&c[0], &c[1]),
+                fe2(&c[2], &c[3]),
+            )))
+        };
+        let proof_g1 = |a: &G1Affine| g1(&[to_mont(&a.0), to_mont(&a.1), r1.clone()]);
+        let proof_g2 = |b: &G2Affine| match b {
+            G2Affine::Fq2(G2AffineFq2(x, y)) => g2(&[
+                to_mont(&x.0),
+                to_mont(&x.1),
+                to_mont(&y.0),
+                to_mont(&y.1),
+                r1.clone(),
+                zero.clone(),
+            ]),
+            G2Affine::Fq(_) => None,
+        };
+
+        let verify = || -> Option<bool> {
+            let (alpha, beta, gamma, delta) = (
+                g1(&points[0])?,
+                g2(&points[1])?,
+                g2(&points[2])?,
+                g2(&points[3])?,
+            );
+            let gamma_abc = points[4..]
+                .iter()
+                .map(|c| g1(c))
+                .collect::<Option<Vec<_>>>()?;
+            if gamma_abc.len() != proof.inputs.len() + 1 {
+                return None;
+            }
+
+            let mut vk_x = gamma_abc[0].into_projective();
+            for (input, g) in proof.inputs.iter().zip(&gamma_abc[1..]) {
+                let input = BigUint::parse_bytes(input.trim_start_matches("0x").as_bytes(), 16)?;
+                let input = <E as PairingEngine>::Fr::from_le_bytes_mod_order(&input.to_bytes_le());
+                vk_x += g.mul(input.into_repr());
+            }
+
+            // `pairCheck4Point` negates the first G1 point
+            let pairs = [
+                (-proof_g1(&proof.proof.a)?, proof_g2(&proof.proof.b)?),
+                (alpha, beta),
+                (vk_x.into_affine(), gamma),
+                (proof_g1(&proof.proof.c)?, delta),
+            ];
+            let prepared: Vec<_> = pairs
+                .iter()
+                .map(|(a, b)| {
+                    (
+                        <E as PairingEngine>::G1Prepared::from(*a),
+                        <E as PairingEngine>::G2Prepared::from(*b),
+                    )
+                })
+                .collect();
+            let miller = <E as PairingEngine>::miller_loop(prepared.iter());
+            Some(
+                <E as PairingEngine>::final_exponentiation(&miller)
+                    == Some(<E as PairingEngine>::Fqk::one()),
+            )
+        };
+        verify().unwrap_or(false)
+    }
+
+    #[test]
+    fn scrypt_bls12_381_verifier_matches_pairing() {
+        use zokrates_common::helpers::CurveParameter;
+        use zokrates_proof_systems::{ScryptCompatibleScheme, ScryptExportConfig};
+
+        let program: Prog<Bls12_381Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [15; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bls12_381Field::from(8)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [16; 32]);
+
+        let sections =
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier_sections(
+                keypair.vk,
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+
+        // the pairs are checked in the order the reference pairing takes them
+        assert!(sections.snark_class.contains(
+            "BLS12381Pairing.pairCheck4Point(
+            toPointG1(proof.a), toPointG2(proof.b),
+            vk.alpha, vk.beta,
+            vk_x, vk.gamma,
+            toPointG1(proof.c), vk.delta
+        )"
+        ));
+        assert!(sections
+            .pairing_lib
+            .contains("f0 = millerLoop(BLS12381.NegG1(pair.g1), pair.g2);"));
+
+        // the Montgomery form of one the points are exported with is that of the library
+        let r1 = Regex::new(r"static const fe r1 = 0x([0-9a-f]+);")
+            .unwrap()
+            .captures(&sections.pairing_lib)
+            .unwrap()[1]
+            .to_string();
+        let r1 = num_bigint::BigUint::parse_bytes(r1.as_bytes(), 16).unwrap();
+        let points = bls12_381_scrypt_vk(&sections.verifying_key_data);
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0][2], r1);
+        assert_eq!(points[1][4], r1);
+
+        // the `fe2` components are exported as `[c0, c1]`
+        assert!(bls12_381_scrypt_verify(&points, &proof, false));
+        assert!(!bls12_381_scrypt_verify(&points, &proof, true));
+    }
+
+    #[test]
+    fn scrypt_bls12_381_verifier_rejects_tampering() {
+        use zokrates_common::helpers::CurveParameter;
+        use zokrates_proof_systems::{ScryptCompatibleScheme, ScryptExportConfig};
+
+        let program: Prog<Bls12_381Field> = Prog {
+            arguments: vec![
+                Parameter::public(Variable::new(0)),
+                Parameter::private(Variable::new(1)),
+            ],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(1), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [17; 32]);
+        let witness = Interpreter::default()
+            .execute(
+                program.clone(),
+                &[Bls12_381Field::from(3), Bls12_381Field::from(7)],
+            )
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [18; 32]);
+
+        let sections =
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier_sections(
+                keypair.vk,
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+        let points = bls12_381_scrypt_vk(&sections.verifying_key_data);
+        assert_eq!(points.len(), 7);
+        assert!(bls12_381_scrypt_verify(&points, &proof, false));
+
+        // another public input
+        let mut tampered = Proof::new(proof.proof.clone(), proof.inputs.clone());
+        tampered.inputs[0] = format!("0x{:0>64}", "4");
+        assert!(!bls12_381_scrypt_verify(&points, &tampered, false));
+
+        // proof points swapped
+        let mut tampered = Proof::new(proof.proof.clone(), proof.inputs.clone());
+        tampered.proof.a = proof.proof.c.clone();
+        tampered.proof.c = proof.proof.a.clone();
+        assert!(!bls12_381_scrypt_verify(&points, &tampered, false));
+
+        // an exported constant changed, or gammaAbc points reordered
+        let mut tampered = points.clone();
+        tampered[0][0] += 1u32;
+        assert!(!bls12_381_scrypt_verify(&tampered, &proof, false));
+
+        let mut tampered = points.clone();
+        tampered.swap(5, 6);
+        assert!(!bls12_381_scrypt_verify(&tampered, &proof, false));
+
+        // a verifying key exported without Montgomery conversion
+        let mut tampered = points;
+        tampered[0][2] = 1u32.into();
+        assert!(!bls12_381_scrypt_verify(&tampered, &proof, false));
+    }
 }
@@ -165,7 +165,9 @@ mod parse {
     use super::*;
     use ark_ff::ToBytes;
     use zokrates_field::G2Type;
-    use zokrates_proof_systems::{Fr, G1Affine, G2Affine, G2AffineFq, G2AffineFq2};
+    use zokrates_proof_systems::{
+        CompressedG1Affine, Fr, G1Affine, G2Affine, G2AffineFq, G2AffineFq2,
+    };
 
     pub fn parse_g1<T: Field + ArkFieldExtensions>(
         e: &<T::ArkEngine as PairingEngine>::G1Affine,
@@ -188,6 +190,15 @@ mod parse {
         )
     }
 
+    /// Compresses a G1 point to its x coordinate and the parity of its y coordinate
+    pub fn parse_g1_compressed<T: Field + ArkFieldExtensions>(
+        e: &<T::ArkEngine as PairingEngine>::G1Affine,
+    ) -> CompressedG1Affine {
+        let G1Affine(x, y) = parse_g1::<T>(e);
+        let y_odd = u8::from_str_radix(&y[y.len() - 1..], 16).unwrap() & 1 == 1;
+        CompressedG1Affine(x, y_odd)
+    }
+
     pub fn parse_g2<T: Field + ArkFieldExtensions>(
         e: &<T::ArkEngine as PairingEngine>::G2Affine,
     ) -> G2Affine {
@@ -247,45 +258,136 @@ mod parse {
 }
 
 pub mod serialization {
+    use crate::parse_g1_compressed;
     use ark_ec::PairingEngine;
     use ark_ff::FromBytes;
-    use zokrates_field::ArkFieldExtensions;
-    use zokrates_proof_systems::{G1Affine, G2Affine};
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use zokrates_field::{ArkFieldExtensions, Field};
+    use zokrates_proof_systems::{CompressedG1Affine, G1Affine, G2Affine};
 
     #[inline]
-    fn decode_hex(value: String) -> Vec<u8> {
-        let mut bytes = hex::decode(value.strip_prefix("0x").unwrap()).unwrap();
+    fn try_decode_hex(value: &str) -> Option<Vec<u8>> {
+        let mut bytes = hex::decode(value.strip_prefix("0x")?).ok()?;
         bytes.reverse();
-        bytes
+        Some(bytes)
     }
 
-    pub fn to_g1<T: ArkFieldExtensions>(g1: G1Affine) -> <T::ArkEngine as PairingEngine>::G1Affine {
+    /// Checks that a point is on the curve and in the prime order subgroup. The compressed
+    /// encoding only keeps `x` and the sign of `y`, and decoding it recomputes `y` and checks
+    /// the subgroup, so only a valid point survives the round trip
+    pub fn is_valid_point<G: CanonicalSerialize + CanonicalDeserialize + PartialEq>(
+        point: &G,
+    ) -> bool {
         let mut bytes = vec![];
-        bytes.append(&mut decode_hex(g1.0));
-        bytes.append(&mut decode_hex(g1.1));
+        point.serialize(&mut bytes).is_ok()
+            && G::deserialize(&*bytes).map_or(false, |p| p == *point)
+    }
+
+    fn try_to_g1<T: ArkFieldExtensions>(
+        g1: G1Affine,
+    ) -> Option<<T::ArkEngine as PairingEngine>::G1Affine> {
+        let mut bytes = vec![];
+        bytes.append(&mut try_decode_hex(&g1.0)?);
+        bytes.append(&mut try_decode_hex(&g1.1)?);
         bytes.push(0u8); // infinity flag
 
-        <T::ArkEngine as PairingEngine>::G1Affine::read(&*bytes).unwrap()
+        <T::ArkEngine as PairingEngine>::G1Affine::read(&*bytes).ok()
     }
 
-    pub fn to_g2<T: ArkFieldExtensions>(g2: G2Affine) -> <T::ArkEngine as PairingEngine>::G2Affine {
+    pub fn to_g1<T: ArkFieldExtensions>(g1: G1Affine) -> <T::ArkEngine as PairingEngine>::G1Affine {
+        try_to_g1::<T>(g1).unwrap()
+    }
+
+    /// Reads an untrusted G1 point, returning `None` unless it is a valid group element
+    pub fn to_valid_g1<T: ArkFieldExtensions>(
+        g1: G1Affine,
+    ) -> Option<<T::ArkEngine as PairingEngine>::G1Affine> {
+        try_to_g1::<T>(g1).filter(is_valid_point)
+    }
+
+    /// Decompresses a G1 point, as `BN256.unpackG1Point` does on-chain
+    pub fn to_g1_compressed<T: Field + ArkFieldExtensions>(
+        g1: CompressedG1Affine,
+    ) -> Result<<T::ArkEngine as PairingEngine>::G1Affine, String> {
+        // without sign flags, ark recovers one of the two points with this x coordinate
+        let bytes =
+            try_decode_hex(&g1.0).ok_or_else(|| format!("invalid coordinate: `{}`", g1.0))?;
+        let point = <T::ArkEngine as PairingEngine>::G1Affine::deserialize(&*bytes)
+            .map_err(|_| format!("No G1 point with x coordinate {}", g1.0))?;
+
+        if parse_g1_compressed::<T>(&point).1 == g1.1 {
+            Ok(point)
+        } else {
+            Ok(-point)
+        }
+    }
+
+    fn try_to_g2<T: ArkFieldExtensions>(
+        g2: G2Affine,
+    ) -> Option<<T::ArkEngine as PairingEngine>::G2Affine> {
         let mut bytes = vec![];
 
         match g2 {
             G2Affine::Fq(g2) => {
-                bytes.append(&mut decode_hex(g2.0));
-                bytes.append(&mut decode_hex(g2.1));
+                bytes.append(&mut try_decode_hex(&g2.0)?);
+                bytes.append(&mut try_decode_hex(&g2.1)?);
                 bytes.push(0u8); // infinity flag
             }
             G2Affine::Fq2(g2) => {
-                bytes.append(&mut decode_hex((g2.0).0));
-                bytes.append(&mut decode_hex((g2.0).1));
-                bytes.append(&mut decode_hex((g2.1).0));
-                bytes.append(&mut decode_hex((g2.1).1));
+                bytes.append(&mut try_decode_hex(&(g2.0).0)?);
+                bytes.append(&mut try_decode_hex(&(g2.0).1)?);
+                bytes.append(&mut try_decode_hex(&(g2.1).0)?);
+                bytes.append(&mut try_decode_hex(&(g2.1).1)?);
                 bytes.push(0u8); // infinity flag
             }
         };
 
-        <T::ArkEngine as PairingEngine>::G2Affine::read(&*bytes).unwrap()
+        <T::ArkEngine as PairingEngine>::G2Affine::read(&*bytes).ok()
+    }
+
+    pub fn to_g2<T: ArkFieldExtensions>(g2: G2Affine) -> <T::ArkEngine as PairingEngine>::G2Affine {
+        try_to_g2::<T>(g2).unwrap()
+    }
+
+    /// Reads an untrusted G2 point, returning `None` unless it is a valid group element
+    pub fn to_valid_g2<T: ArkFieldExtensions>(
+        g2: G2Affine,
+    ) -> Option<<T::ArkEngine as PairingEngine>::G2Affine> {
+        try_to_g2::<T>(g2).filter(is_valid_point)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ark_ec::{AffineCurve, ProjectiveCurve};
+    use zokrates_field::{Bls12_381Field, Bn128Field};
+
+    fn compressed_round_trip<T: Field + ArkFieldExtensions>() {
+        let g = <T::ArkEngine as PairingEngine>::G1Affine::prime_subgroup_generator();
+        let p = g.mul(<T::ArkEngine as PairingEngine>::Fr::from(42u64)).into_affine();
+
+        // both signs of y, so that both parities are covered
+        for point in [p, -p] {
+            let compressed = parse_g1_compressed::<T>(&point);
+            let decompressed = serialization::to_g1_compressed::<T>(compressed).unwrap();
+            assert_eq!(decompressed, point);
+        }
+        assert_ne!(
+            parse_g1_compressed::<T>(&p).1,
+            parse_g1_compressed::<T>(&-p).1
+        );
+
+        // malformed hex is an error rather than a panic
+        for x in ["0xzz", "42", "0x123"] {
+            let compressed = zokrates_proof_systems::CompressedG1Affine(x.into(), false);
+            assert!(serialization::to_g1_compressed::<T>(compressed).is_err());
+        }
+    }
+
+    #[test]
+    fn compressed_g1_round_trip() {
+        compressed_round_trip::<Bn128Field>();
+        compressed_round_trip::<Bls12_381Field>();
     }
 }
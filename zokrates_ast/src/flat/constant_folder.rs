@@ -0,0 +1,168 @@
+//! Module containing the `ConstantFolder`, which simplifies flat programs by
+//! * folding operations on two numbers
+//! * removing additions of zero and multiplications by one
+//! * removing conditions of the form `x == x`
+//!
+//! Folding never introduces a product of two variables, so linear expressions stay linear.
+
+use super::folder::{fold_expression, fold_statement, Folder};
+use super::*;
+use zokrates_field::Field;
+
+#[derive(Default)]
+pub struct ConstantFolder;
+
+impl ConstantFolder {
+    pub fn fold<T: Field>(p: FlatProg<T>) -> FlatProg<T> {
+        ConstantFolder.fold_program(p)
+    }
+}
+
+impl<T: Field> Folder<T> for ConstantFolder {
+    fn fold_statement(&mut self, s: FlatStatement<T>) -> Vec<FlatStatement<T>> {
+        fold_statement(self, s)
+            .into_iter()
+            .filter(|s| !matches!(s, FlatStatement::Condition(left, right, _) if left == right))
+            .collect()
+    }
+
+    fn fold_expression(&mut self, e: FlatExpression<T>) -> FlatExpression<T> {
+        match fold_expression(self, e) {
+            FlatExpression::Add(
+                box FlatExpression::Number(left),
+                box FlatExpression::Number(right),
+            ) => FlatExpression::Number(left + right),
+            FlatExpression::Sub(
+                box FlatExpression::Number(left),
+                box FlatExpression::Number(right),
+            ) => FlatExpression::Number(left - right),
+            FlatExpression::Mult(
+                box FlatExpression::Number(left),
+                box FlatExpression::Number(right),
+            ) => FlatExpression::Number(left * right),
+            FlatExpression::Add(box FlatExpression::Number(n), box e)
+            | FlatExpression::Add(box e, box FlatExpression::Number(n))
+            | FlatExpression::Sub(box e, box FlatExpression::Number(n))
+                if n == T::zero() =>
+            {
+                e
+            }
+            FlatExpression::Mult(box FlatExpression::Number(n), box e)
+            | FlatExpression::Mult(box e, box FlatExpression::Number(n))
+                if n == T::one() =>
+            {
+                e
+            }
+            FlatExpression::Mult(box FlatExpression::Number(n), _)
+            | FlatExpression::Mult(_, box FlatExpression::Number(n))
+                if n == T::zero() =>
+            {
+                FlatExpression::Number(T::zero())
+            }
+            e => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn multiplications(e: &FlatExpression<Bn128Field>) -> usize {
+        match e {
+            FlatExpression::Number(_) | FlatExpression::Identifier(_) => 0,
+            FlatExpression::Add(left, right) | FlatExpression::Sub(left, right) => {
+                multiplications(left) + multiplications(right)
+            }
+            FlatExpression::Mult(left, right) | FlatExpression::Div(left, right) => {
+                1 + multiplications(left) + multiplications(right)
+            }
+        }
+    }
+
+    fn gates(p: &FlatProg<Bn128Field>) -> usize {
+        p.statements
+            .iter()
+            .map(|s| match s {
+                FlatStatement::Definition(_, e) => 1 + multiplications(e),
+                FlatStatement::Condition(left, right, _) => {
+                    1 + multiplications(left) + multiplications(right)
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    #[test]
+    fn fold_constants() {
+        let x = Variable::new(0);
+        let y = Variable::new(1);
+        let z = Variable::new(2);
+
+        let p: FlatProg<Bn128Field> = FlatProg {
+            arguments: vec![Parameter::private(x)],
+            statements: vec![
+                // y = 1 * x
+                FlatStatement::Definition(
+                    y,
+                    FlatExpression::Mult(
+                        box FlatExpression::Number(Bn128Field::from(1)),
+                        box FlatExpression::Identifier(x),
+                    ),
+                ),
+                // z = 0 + (2 * 3) * y
+                FlatStatement::Definition(
+                    z,
+                    FlatExpression::Add(
+                        box FlatExpression::Number(Bn128Field::from(0)),
+                        box FlatExpression::Mult(
+                            box FlatExpression::Mult(
+                                box FlatExpression::Number(Bn128Field::from(2)),
+                                box FlatExpression::Number(Bn128Field::from(3)),
+                            ),
+                            box FlatExpression::Identifier(y),
+                        ),
+                    ),
+                ),
+                // y - 0 == y
+                FlatStatement::Condition(
+                    FlatExpression::Sub(
+                        box FlatExpression::Identifier(y),
+                        box FlatExpression::Number(Bn128Field::from(0)),
+                    ),
+                    FlatExpression::Identifier(y),
+                    RuntimeError::Equal,
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(z),
+                    FlatExpression::Identifier(Variable::public(0)),
+                    RuntimeError::Equal,
+                ),
+            ],
+            return_count: 1,
+        };
+
+        let folded = ConstantFolder::fold(p.clone());
+
+        assert_eq!(
+            folded.statements,
+            vec![
+                FlatStatement::Definition(y, FlatExpression::Identifier(x)),
+                FlatStatement::Definition(
+                    z,
+                    FlatExpression::Mult(
+                        box FlatExpression::Number(Bn128Field::from(6)),
+                        box FlatExpression::Identifier(y),
+                    ),
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(z),
+                    FlatExpression::Identifier(Variable::public(0)),
+                    RuntimeError::Equal,
+                ),
+            ]
+        );
+        assert!(gates(&folded) < gates(&p));
+    }
+}
@@ -0,0 +1,212 @@
+//! Module containing the `DivLowerer`, which replaces each division `n / d` of a flat program
+//! by `n * inv`, where `inv` is given by a directive and constrained by `d * inv == 1`.
+//! A zero denominator therefore fails with `RuntimeError::Inverse`.
+
+use super::folder::{fold_expression, fold_statement, Folder};
+use super::*;
+use zokrates_field::Field;
+
+pub struct DivLowerer<T> {
+    next_id: usize,
+    statements: Vec<FlatStatement<T>>,
+}
+
+impl<T: Field> DivLowerer<T> {
+    pub fn lower(p: FlatProg<T>) -> FlatProg<T> {
+        // every variable is an argument, defined or the output of a directive
+        let next_id = p
+            .arguments
+            .iter()
+            .map(|a| a.id)
+            .chain(p.statements.iter().flat_map(|s| match s {
+                FlatStatement::Definition(v, _) => vec![*v],
+                FlatStatement::Directive(d) => d.outputs.clone(),
+                _ => vec![],
+            }))
+            .filter(|v| v.id > 0)
+            .map(|v| v.id() + 1)
+            .max()
+            .unwrap_or(0);
+
+        DivLowerer {
+            next_id,
+            statements: vec![],
+        }
+        .fold_program(p)
+    }
+
+    /// Lowers the divisions of a single statement, numbering the introduced variables from
+    /// `next_id` on and advancing it past them, so that a program can be lowered as it streams
+    pub fn lower_statement(next_id: &mut usize, s: FlatStatement<T>) -> Vec<FlatStatement<T>> {
+        let mut lowerer = DivLowerer {
+            next_id: *next_id,
+            statements: vec![],
+        };
+        let statements = lowerer.fold_statement(s);
+        *next_id = lowerer.next_id;
+        statements
+    }
+
+    fn fresh(&mut self) -> Variable {
+        let v = Variable::new(self.next_id);
+        self.next_id += 1;
+        v
+    }
+
+    /// Defines `e` as a new variable unless it is linear
+    fn define(&mut self, e: FlatExpression<T>) -> FlatExpression<T> {
+        match e.is_linear() {
+            true => e,
+            false => {
+                let v = self.fresh();
+                self.statements.push(FlatStatement::Definition(v, e));
+                v.into()
+            }
+        }
+    }
+}
+
+impl<T: Field> Folder<T> for DivLowerer<T> {
+    fn fold_statement(&mut self, s: FlatStatement<T>) -> Vec<FlatStatement<T>> {
+        let s = fold_statement(self, s);
+        self.statements.drain(..).chain(s).collect()
+    }
+
+    fn fold_expression(&mut self, e: FlatExpression<T>) -> FlatExpression<T> {
+        match fold_expression(self, e) {
+            FlatExpression::Div(box numerator, box denominator) => {
+                let numerator = self.define(numerator);
+                let denominator = self.define(denominator);
+                let inverse = self.fresh();
+
+                // # inverse = 1/denominator
+                self.statements
+                    .push(FlatStatement::Directive(FlatDirective::new(
+                        vec![inverse],
+                        Solver::Div,
+                        vec![FlatExpression::Number(T::one()), denominator.clone()],
+                    )));
+
+                // assert(inverse * denominator == 1)
+                self.statements.push(FlatStatement::Condition(
+                    FlatExpression::Number(T::one()),
+                    FlatExpression::Mult(box denominator, box inverse.into()),
+                    RuntimeError::Inverse,
+                ));
+
+                let quotient = self.fresh();
+                self.statements.push(FlatStatement::Definition(
+                    quotient,
+                    FlatExpression::Mult(box numerator, box inverse.into()),
+                ));
+
+                quotient.into()
+            }
+            e => e,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn lower_div() {
+        let a = Variable::new(0);
+        let b = Variable::new(1);
+        let c = Variable::new(2);
+
+        let p: FlatProg<Bn128Field> = FlatProg {
+            arguments: vec![Parameter::private(a), Parameter::private(b)],
+            statements: vec![
+                // c = a / b
+                FlatStatement::Definition(
+                    c,
+                    FlatExpression::Div(
+                        box FlatExpression::Identifier(a),
+                        box FlatExpression::Identifier(b),
+                    ),
+                ),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(c),
+                    FlatExpression::Identifier(Variable::public(0)),
+                    RuntimeError::Equal,
+                ),
+            ],
+            return_count: 1,
+        };
+
+        let inverse = Variable::new(3);
+        let quotient = Variable::new(4);
+
+        assert_eq!(
+            DivLowerer::lower(p).statements,
+            vec![
+                FlatStatement::Directive(FlatDirective::new(
+                    vec![inverse],
+                    Solver::Div,
+                    vec![
+                        FlatExpression::Number(Bn128Field::from(1)),
+                        FlatExpression::Identifier(b),
+                    ],
+                )),
+                FlatStatement::Condition(
+                    FlatExpression::Number(Bn128Field::from(1)),
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(b),
+                        box FlatExpression::Identifier(inverse),
+                    ),
+                    RuntimeError::Inverse,
+                ),
+                FlatStatement::Definition(
+                    quotient,
+                    FlatExpression::Mult(
+                        box FlatExpression::Identifier(a),
+                        box FlatExpression::Identifier(inverse),
+                    ),
+                ),
+                FlatStatement::Definition(c, FlatExpression::Identifier(quotient)),
+                FlatStatement::Condition(
+                    FlatExpression::Identifier(c),
+                    FlatExpression::Identifier(Variable::public(0)),
+                    RuntimeError::Equal,
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn lower_statement_advances_next_id() {
+        let a = Variable::new(0);
+        let b = Variable::new(1);
+        let c = Variable::new(2);
+
+        let s: FlatStatement<Bn128Field> = FlatStatement::Definition(
+            c,
+            FlatExpression::Div(
+                box FlatExpression::Identifier(a),
+                box FlatExpression::Identifier(b),
+            ),
+        );
+
+        let mut next_id = 3;
+        let statements = DivLowerer::lower_statement(&mut next_id, s);
+        assert_eq!(next_id, 5);
+        assert_eq!(statements.len(), 4);
+        assert_eq!(
+            statements[3],
+            FlatStatement::Definition(c, FlatExpression::Identifier(Variable::new(4)))
+        );
+
+        // statements without division are left as they are
+        let s: FlatStatement<Bn128Field> =
+            FlatStatement::Definition(c, FlatExpression::Identifier(a));
+        assert_eq!(
+            DivLowerer::lower_statement(&mut next_id, s.clone()),
+            vec![s]
+        );
+        assert_eq!(next_id, 5);
+    }
+}
@@ -86,6 +86,9 @@ pub fn fold_expression<T: Field, F: Folder<T>>(
         FlatExpression::Mult(box left, box right) => {
             FlatExpression::Mult(box f.fold_expression(left), box f.fold_expression(right))
         }
+        FlatExpression::Div(box left, box right) => {
+            FlatExpression::Div(box f.fold_expression(left), box f.fold_expression(right))
+        }
     }
 }
 
@@ -5,6 +5,8 @@
 //! @author Jacob Eberhardt <jacob.eberhardt@tu-berlin.de>
 //! @date 2017
 
+pub mod constant_folder;
+pub mod div_lowering;
 pub mod folder;
 pub mod utils;
 
@@ -19,6 +21,7 @@ pub use utils::{
 };
 
 use crate::common::Solver;
+use crate::ir::Witness;
 use crate::typed::ConcreteType;
 use std::collections::HashMap;
 use std::fmt;
@@ -70,6 +73,78 @@ impl<T: Field> fmt::Display for FlatFunction<T> {
     }
 }
 
+impl<T: Field> FlatFunction<T> {
+    /// Counts the statements, operations and arguments of the function
+    pub fn summary(&self) -> FlatProgSummary {
+        let mut summary = FlatProgSummary {
+            returns: self.return_count,
+            public_arguments: self.arguments.iter().filter(|a| !a.private).count(),
+            private_arguments: self.arguments.iter().filter(|a| a.private).count(),
+            ..FlatProgSummary::default()
+        };
+
+        for statement in &self.statements {
+            match statement {
+                FlatStatement::Definition(_, e) => {
+                    summary.definitions += 1;
+                    e.count_operations(&mut summary);
+                }
+                FlatStatement::Condition(left, right, _) => {
+                    summary.conditions += 1;
+                    left.count_operations(&mut summary);
+                    right.count_operations(&mut summary);
+                }
+                FlatStatement::Directive(d) => {
+                    summary.directives += 1;
+                    d.inputs
+                        .iter()
+                        .for_each(|e| e.count_operations(&mut summary));
+                }
+                FlatStatement::Log(..) => summary.logs += 1,
+            }
+        }
+
+        summary
+    }
+}
+
+/// Statement, operation and argument counts of a flat program
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FlatProgSummary {
+    pub definitions: usize,
+    pub conditions: usize,
+    pub directives: usize,
+    pub logs: usize,
+    pub returns: usize,
+    pub additions: usize,
+    pub subtractions: usize,
+    pub multiplications: usize,
+    pub divisions: usize,
+    pub public_arguments: usize,
+    pub private_arguments: usize,
+}
+
+impl fmt::Display for FlatProgSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "arguments: {} public, {} private",
+            self.public_arguments, self.private_arguments
+        )?;
+        writeln!(f, "returns: {}", self.returns)?;
+        writeln!(
+            f,
+            "statements: {} definitions, {} conditions, {} directives, {} logs",
+            self.definitions, self.conditions, self.directives, self.logs
+        )?;
+        write!(
+            f,
+            "operations: {} additions, {} subtractions, {} multiplications, {} divisions",
+            self.additions, self.subtractions, self.multiplications, self.divisions
+        )
+    }
+}
+
 /// Calculates a flattened function based on a R1CS (A, B, C) and returns that flattened function:
 /// * The Rank 1 Constraint System (R1CS) is defined as:
 /// * `<A,x>*<B,x> = <C,x>` for a witness `x`
@@ -217,6 +292,7 @@ pub enum FlatExpression<T> {
     Add(Box<FlatExpression<T>>, Box<FlatExpression<T>>),
     Sub(Box<FlatExpression<T>>, Box<FlatExpression<T>>),
     Mult(Box<FlatExpression<T>>, Box<FlatExpression<T>>),
+    Div(Box<FlatExpression<T>>, Box<FlatExpression<T>>),
 }
 
 impl<T> From<T> for FlatExpression<T> {
@@ -247,6 +323,58 @@ impl<T: Field> FlatExpression<T> {
                 box e1.apply_substitution(substitution),
                 box e2.apply_substitution(substitution),
             ),
+            FlatExpression::Div(e1, e2) => FlatExpression::Div(
+                box e1.apply_substitution(substitution),
+                box e2.apply_substitution(substitution),
+            ),
+        }
+    }
+
+    fn count_operations(&self, summary: &mut FlatProgSummary) {
+        match self {
+            FlatExpression::Number(_) | FlatExpression::Identifier(_) => {}
+            FlatExpression::Add(e1, e2) => {
+                summary.additions += 1;
+                e1.count_operations(summary);
+                e2.count_operations(summary);
+            }
+            FlatExpression::Sub(e1, e2) => {
+                summary.subtractions += 1;
+                e1.count_operations(summary);
+                e2.count_operations(summary);
+            }
+            FlatExpression::Mult(e1, e2) => {
+                summary.multiplications += 1;
+                e1.count_operations(summary);
+                e2.count_operations(summary);
+            }
+            FlatExpression::Div(e1, e2) => {
+                summary.divisions += 1;
+                e1.count_operations(summary);
+                e2.count_operations(summary);
+            }
+        }
+    }
+
+    /// Evaluates the expression, looking up its variables in `witness`
+    pub fn evaluate(&self, witness: &Witness<T>) -> Result<T, Error> {
+        match self {
+            FlatExpression::Number(n) => Ok(n.clone()),
+            FlatExpression::Identifier(v) => witness.0.get(v).cloned().ok_or_else(|| Error {
+                message: format!("Variable {} is not in the witness", v),
+            }),
+            FlatExpression::Add(e1, e2) => Ok(e1.evaluate(witness)? + e2.evaluate(witness)?),
+            FlatExpression::Sub(e1, e2) => Ok(e1.evaluate(witness)? - e2.evaluate(witness)?),
+            FlatExpression::Mult(e1, e2) => Ok(e1.evaluate(witness)? * e2.evaluate(witness)?),
+            FlatExpression::Div(e1, e2) => {
+                let denominator = e2.evaluate(witness)?;
+                if denominator == T::zero() {
+                    return Err(Error {
+                        message: RuntimeError::Inverse.to_string(),
+                    });
+                }
+                Ok(e1.evaluate(witness)? / denominator)
+            }
         }
     }
 
@@ -268,6 +396,7 @@ impl<T: Field> FlatExpression<T> {
                         box FlatExpression::Number(_)
                     )
             ),
+            FlatExpression::Div(..) => false,
         }
     }
 }
@@ -280,6 +409,7 @@ impl<T: Field> fmt::Display for FlatExpression<T> {
             FlatExpression::Add(ref lhs, ref rhs) => write!(f, "({} + {})", lhs, rhs),
             FlatExpression::Sub(ref lhs, ref rhs) => write!(f, "({} - {})", lhs, rhs),
             FlatExpression::Mult(ref lhs, ref rhs) => write!(f, "({} * {})", lhs, rhs),
+            FlatExpression::Div(ref lhs, ref rhs) => write!(f, "({} / {})", lhs, rhs),
         }
     }
 }
@@ -300,3 +430,123 @@ impl fmt::Display for Error {
         write!(f, "{}", self.message)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    #[test]
+    fn summary() {
+        let x = Variable::new(0);
+        let y = Variable::new(1);
+        let z = Variable::new(2);
+
+        let p: FlatProg<Bn128Field> = FlatProg {
+            arguments: vec![Parameter::private(x), Parameter::public(y)],
+            statements: vec![
+                // z = (x + y) * x
+                FlatStatement::Definition(
+                    z,
+                    FlatExpression::Mult(
+                        box FlatExpression::Add(
+                            box FlatExpression::Identifier(x),
+                            box FlatExpression::Identifier(y),
+                        ),
+                        box FlatExpression::Identifier(x),
+                    ),
+                ),
+                // z - y == ~out_0
+                FlatStatement::Condition(
+                    FlatExpression::Sub(
+                        box FlatExpression::Identifier(z),
+                        box FlatExpression::Identifier(y),
+                    ),
+                    FlatExpression::Identifier(Variable::public(0)),
+                    RuntimeError::Equal,
+                ),
+            ],
+            return_count: 1,
+        };
+
+        assert_eq!(
+            p.summary(),
+            FlatProgSummary {
+                definitions: 1,
+                conditions: 1,
+                directives: 0,
+                logs: 0,
+                returns: 1,
+                additions: 1,
+                subtractions: 1,
+                multiplications: 1,
+                divisions: 0,
+                public_arguments: 1,
+                private_arguments: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn evaluate_nested() {
+        let a = Variable::new(0);
+        let b = Variable::new(1);
+        let c = Variable::public(0);
+        let witness = Witness(
+            vec![
+                (a, Bn128Field::from(2)),
+                (b, Bn128Field::from(3)),
+                (c, Bn128Field::from(7)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        // (a + b) * c
+        let e = FlatExpression::Mult(
+            box FlatExpression::Add(
+                box FlatExpression::Identifier(a),
+                box FlatExpression::Identifier(b),
+            ),
+            box FlatExpression::Identifier(c),
+        );
+        assert_eq!(e.evaluate(&witness), Ok(Bn128Field::from(35)));
+
+        // (a + b) * c - 5
+        let e = FlatExpression::Sub(box e, box FlatExpression::Number(Bn128Field::from(5)));
+        assert_eq!(e.evaluate(&witness), Ok(Bn128Field::from(30)));
+
+        let missing = FlatExpression::Identifier(Variable::new(42));
+        assert!(missing.evaluate(&witness).is_err());
+    }
+
+    #[test]
+    fn evaluate_div() {
+        let a = Variable::new(0);
+        let b = Variable::new(1);
+        let witness = Witness(
+            vec![(a, Bn128Field::from(6)), (b, Bn128Field::from(0))]
+                .into_iter()
+                .collect(),
+        );
+
+        // a / 3
+        let e = FlatExpression::Div(
+            box FlatExpression::Identifier(a),
+            box FlatExpression::Number(Bn128Field::from(3)),
+        );
+        assert!(!e.is_linear());
+        assert_eq!(e.to_string(), "(_0 / 3)");
+        assert_eq!(e.evaluate(&witness), Ok(Bn128Field::from(2)));
+
+        // a / b
+        let e = FlatExpression::Div(
+            box FlatExpression::Identifier(a),
+            box FlatExpression::Identifier(b),
+        );
+        assert_eq!(
+            e.evaluate(&witness).unwrap_err().to_string(),
+            "Division by zero"
+        );
+    }
+}
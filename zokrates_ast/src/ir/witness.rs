@@ -1,4 +1,4 @@
-use crate::common::Variable;
+use crate::common::{Parameter, Variable};
 use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 use std::io;
@@ -41,6 +41,32 @@ impl<T: Field> Witness<T> {
         Witness(BTreeMap::new())
     }
 
+    /// Checks that the witness assigns every argument and return value of a program
+    /// with the given signature. Only the signature is checked: the statements are not
+    /// walked, so a witness of another program with the same signature is not detected.
+    pub fn check_signature(
+        &self,
+        arguments: &[Parameter],
+        return_count: usize,
+    ) -> Result<(), String> {
+        let missing = arguments
+            .iter()
+            .map(|p| p.id)
+            .chain((0..return_count).map(Variable::public))
+            .filter(|v| !self.0.contains_key(v))
+            .map(|v| v.to_string())
+            .collect::<Vec<_>>();
+
+        if missing.is_empty() {
+            Ok(())
+        } else {
+            Err(format!(
+                "Witness does not match the program signature: no value for {}",
+                missing.join(", ")
+            ))
+        }
+    }
+
     pub fn write<W: Write>(&self, writer: W) -> io::Result<()> {
         let mut wtr = csv::WriterBuilder::new()
             .delimiter(b' ')
@@ -166,4 +192,37 @@ mod tests {
             assert!(Witness::<Bn128Field>::read(buff).is_err());
         }
     }
+
+    #[test]
+    fn check_signature() {
+        let w = Witness(
+            vec![
+                (Variable::new(0), Bn128Field::from(42)),
+                (Variable::public(0), Bn128Field::from(42)),
+                (Variable::one(), Bn128Field::from(1)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        assert!(w
+            .check_signature(&[Parameter::private(Variable::new(0))], 1)
+            .is_ok());
+
+        // a program with another argument
+        assert!(w
+            .check_signature(
+                &[
+                    Parameter::private(Variable::new(0)),
+                    Parameter::public(Variable::new(1))
+                ],
+                1
+            )
+            .is_err());
+
+        // a program with more return values
+        assert!(w
+            .check_signature(&[Parameter::private(Variable::new(0))], 2)
+            .is_err());
+    }
 }
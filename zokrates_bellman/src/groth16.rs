@@ -18,7 +18,7 @@ use rand_0_4::Rng;
 use std::io::{Read, Write};
 use zokrates_ast::ir::{ProgIterator, Statement, Witness};
 use zokrates_proof_systems::groth16::{ProofPoints, VerificationKey, G16};
-use zokrates_proof_systems::Scheme;
+use zokrates_proof_systems::{fq12_to_scrypt_string, Scheme};
 
 const G16_WARNING: &str = "WARNING: You are using the G16 scheme which is subject to malleability. See zokrates.github.io/toolbox/proving_schemes.html#g16-malleability for implications.";
 
@@ -110,53 +110,12 @@ impl<T: Field + BellmanFieldExtensions> Backend<T, G16> for Bellman {
         let text = alpha_g1_beta_g2.to_string();
         //let caps: regex::Captures = re.captures(&text).unwrap();
         
-        let captures = re.captures_iter(&text);
-        let vals: Vec<_> = captures.collect();
-
-        return format!(
-            r#"{{
-                x: {{
-                    x: {{
-                        x: {}n,
-                        y: {}n
-                    }},
-                    y: {{
-                        x: {}n,
-                        y: {}n
-                    }},
-                    z: {{
-                        x: {}n,
-                        y: {}n
-                    }}
-                }},
-                y: {{
-                    x: {{
-                        x: {}n,
-                        y: {}n
-                    }},
-                    y: {{
-                        x: {}n,
-                        y: {}n
-                    }},
-                    z: {{
-                        x: {}n,
-                        y: {}n
-                    }}
-                }}
-            }}"#,
-            hex_to_decimal(vals[11].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[10].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[9].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[8].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[5].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[6].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[5].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[4].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[3].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[2].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[1].get(1).unwrap().as_str()).unwrap(),
-            hex_to_decimal(vals[0].get(1).unwrap().as_str()).unwrap()
-        );
+        let limbs: Vec<String> = re
+            .captures_iter(&text)
+            .map(|c| hex_to_decimal(c.get(1).unwrap().as_str()).unwrap())
+            .collect();
+
+        fq12_to_scrypt_string(&limbs)
     }
 
 }
@@ -306,4 +265,22 @@ mod tests {
 
         assert!(ans);
     }
+
+    #[test]
+    fn miller_beta_alpha_string() {
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+
+        let keypair = <Bellman as NonUniversalBackend<Bn128Field, G16>>::setup(program);
+        let miller =
+            <Bellman as Backend<Bn128Field, G16>>::get_miller_beta_alpha_string(keypair.vk);
+
+        // same layout as the other backends, so the `millerb1a1` literals are interchangeable
+        let limbs = Regex::new(r"(\d+)n").unwrap();
+        assert_eq!(limbs.find_iter(&miller).count(), 12);
+        assert!(miller.lines().all(|line| !line.ends_with(' ')));
+    }
 }
@@ -53,9 +53,15 @@ fn cli() -> Result<(), String> {
             setup::subcommand(),
             export_verifier::subcommand(),
             export_verifier_scrypt::subcommand(),
+            pairing_lib_info::subcommand(),
+            import_scrypt_vk::subcommand(),
+            #[cfg(any(feature = "bellman", feature = "ark"))]
+            bench_export::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark"))]
             generate_proof::subcommand(),
             generate_smtlib2::subcommand(),
+            #[cfg(feature = "ark")]
+            gen_test_vectors::subcommand(),
             print_proof::subcommand(),
             #[cfg(any(feature = "bellman", feature = "ark"))]
             verify::subcommand()])
@@ -74,9 +80,15 @@ fn cli() -> Result<(), String> {
         ("setup", Some(sub_matches)) => setup::exec(sub_matches),
         ("export-verifier", Some(sub_matches)) => export_verifier::exec(sub_matches),
         ("export-verifier-scrypt", Some(sub_matches)) => export_verifier_scrypt::exec(sub_matches),
+        ("pairing-lib-info", Some(sub_matches)) => pairing_lib_info::exec(sub_matches),
+        ("import-scrypt-vk", Some(sub_matches)) => import_scrypt_vk::exec(sub_matches),
+        #[cfg(any(feature = "bellman", feature = "ark"))]
+        ("bench-export", Some(sub_matches)) => bench_export::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark"))]
         ("generate-proof", Some(sub_matches)) => generate_proof::exec(sub_matches),
         ("generate-smtlib2", Some(sub_matches)) => generate_smtlib2::exec(sub_matches),
+        #[cfg(feature = "ark")]
+        ("gen-test-vectors", Some(sub_matches)) => gen_test_vectors::exec(sub_matches),
         ("print-proof", Some(sub_matches)) => print_proof::exec(sub_matches),
         #[cfg(any(feature = "bellman", feature = "ark"))]
         ("verify", Some(sub_matches)) => verify::exec(sub_matches),
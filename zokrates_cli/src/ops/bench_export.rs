@@ -0,0 +1,137 @@
+use crate::cli_constants;
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+use std::time::{Duration, Instant};
+#[cfg(feature = "ark")]
+use zokrates_ark::Ark;
+#[cfg(feature = "bellman")]
+use zokrates_bellman::Bellman;
+use zokrates_common::constants;
+use zokrates_common::helpers::*;
+use zokrates_field::{Bls12_381Field, Bn128Field};
+use zokrates_proof_systems::*;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("bench-export")
+        .about("Times the stages of exporting a verification key as an sCrypt verifier")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the verification key")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value(cli_constants::VERIFICATION_KEY_DEFAULT_PATH),
+        )
+        .arg(
+            Arg::with_name("backend")
+                .short("b")
+                .long("backend")
+                .help("Backend to use")
+                .takes_value(true)
+                .required(false)
+                .possible_values(cli_constants::BACKENDS)
+                .default_value(constants::BELLMAN),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let vk_path = Path::new(sub_matches.value_of("input").unwrap());
+    let vk_file = File::open(&vk_path)
+        .map_err(|why| format!("Could not open {}: {}", vk_path.display(), why))?;
+
+    // deserialize vk to JSON
+    let vk_reader = BufReader::new(vk_file);
+    let vk: serde_json::Value = serde_json::from_reader(vk_reader)
+        .map_err(|why| format!("Could not deserialize verification key: {}", why))?;
+
+    let vk_curve = vk
+        .get("curve")
+        .ok_or_else(|| "Field `curve` not found in verification key".to_string())?
+        .as_str()
+        .ok_or_else(|| "`curve` should be a string".to_string())?;
+    let vk_scheme = vk
+        .get("scheme")
+        .ok_or_else(|| "Field `scheme` not found in verification key".to_string())?
+        .as_str()
+        .ok_or_else(|| "`scheme` should be a string".to_string())?;
+
+    let parameters = Parameters::try_from((
+        sub_matches.value_of("backend").unwrap(),
+        vk_curve,
+        vk_scheme,
+    ))?;
+
+    let timings = match parameters {
+        #[cfg(feature = "bellman")]
+        Parameters(BackendParameter::Bellman, CurveParameter::Bn128, SchemeParameter::G16) => {
+            cli_bench_export::<Bn128Field, G16, Bellman>(vk, CurveParameter::Bn128)
+        }
+        #[cfg(feature = "bellman")]
+        Parameters(BackendParameter::Bellman, CurveParameter::Bls12_381, SchemeParameter::G16) => {
+            cli_bench_export::<Bls12_381Field, G16, Bellman>(vk, CurveParameter::Bls12_381)
+        }
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, CurveParameter::Bn128, SchemeParameter::G16) => {
+            cli_bench_export::<Bn128Field, G16, Ark>(vk, CurveParameter::Bn128)
+        }
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, CurveParameter::Bls12_381, SchemeParameter::G16) => {
+            cli_bench_export::<Bls12_381Field, G16, Ark>(vk, CurveParameter::Bls12_381)
+        }
+        Parameters(_, curve, scheme) => Err(format!(
+            "Could not benchmark export with given parameters (curve: {}, scheme: {}): not supported",
+            curve, scheme
+        )),
+    }?;
+
+    println!("{}", timings);
+    Ok(())
+}
+
+/// Time spent in each stage of an export
+struct Timings {
+    deserialization: Duration,
+    miller_precompute: Duration,
+    constant_conversion: Duration,
+}
+
+impl std::fmt::Display for Timings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "vk deserialization: {:?}", self.deserialization)?;
+        writeln!(f, "miller precompute: {:?}", self.miller_precompute)?;
+        write!(f, "constant conversion: {:?}", self.constant_conversion)
+    }
+}
+
+fn cli_bench_export<T: ScryptCompatibleField, S: ScryptCompatibleScheme<T>, B: Backend<T, S>>(
+    vk: serde_json::Value,
+    curve_parameter: CurveParameter,
+) -> Result<Timings, String> {
+    let start = Instant::now();
+    let precompute_vk: S::VerificationKey =
+        serde_json::from_value(vk.clone()).map_err(|why| format!("{}", why))?;
+    let deserialization = start.elapsed();
+
+    // the export consumes its verification key, so it gets its own copy
+    let export_vk: S::VerificationKey =
+        serde_json::from_value(vk).map_err(|why| format!("{}", why))?;
+
+    let start = Instant::now();
+    B::get_miller_beta_alpha_string(precompute_vk);
+    let miller_precompute = start.elapsed();
+
+    let start = Instant::now();
+    S::export_scrypt_verifier_sections(export_vk, curve_parameter, &ScryptExportConfig::default())?;
+    let constant_conversion = start.elapsed();
+
+    Ok(Timings {
+        deserialization,
+        miller_precompute,
+        constant_conversion,
+    })
+}
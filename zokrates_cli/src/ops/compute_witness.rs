@@ -47,6 +47,12 @@ pub fn subcommand() -> App<'static, 'static> {
         .takes_value(true)
         .required(false)
         .default_value(cli_constants::CIRCOM_WITNESS_DEFAULT_PATH)
+    ).arg(Arg::with_name("public-inputs")
+        .long("public-inputs")
+        .help("Path of an output JSON file with the public inputs of the proof, in decimal: the public arguments followed by the return values")
+        .value_name("FILE")
+        .takes_value(true)
+        .required(false)
     ).arg(Arg::with_name("arguments")
         .short("a")
         .long("arguments")
@@ -195,6 +201,25 @@ fn cli_compute<T: Field, I: Iterator<Item = ir::Statement<T>>>(
         .write(writer)
         .map_err(|why| format!("Could not save witness: {:?}", why))?;
 
+    // write public inputs to file, in the order of the inputs of a proof
+    if let Some(path) = sub_matches.value_of("public-inputs") {
+        let path = Path::new(path);
+        let values: Vec<String> = public_inputs
+            .iter()
+            .map(|id| witness.0.get(id).unwrap())
+            .chain(witness.return_values().iter())
+            .map(|v| v.to_dec_string())
+            .collect();
+
+        let file = File::create(&path)
+            .map_err(|why| format!("Could not create {}: {}", path.display(), why))?;
+
+        serde_json::to_writer_pretty(BufWriter::new(file), &values)
+            .map_err(|why| format!("Could not save public inputs: {}", why))?;
+
+        println!("Public inputs written to '{}'", path.display());
+    }
+
     // write circom witness to file
     let wtns_path = Path::new(sub_matches.value_of("circom-witness").unwrap());
     let wtns_file = File::create(&wtns_path)
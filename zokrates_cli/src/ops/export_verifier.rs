@@ -5,7 +5,7 @@ use std::fs::File;
 use std::io::{BufReader, BufWriter, Write};
 use std::path::Path;
 use zokrates_common::helpers::{CurveParameter, SchemeParameter};
-use zokrates_field::Bn128Field;
+use zokrates_field::{Bls12_381Field, Bn128Field};
 use zokrates_proof_systems::*;
 
 pub fn subcommand() -> App<'static, 'static> {
@@ -31,6 +31,15 @@ pub fn subcommand() -> App<'static, 'static> {
                 .required(false)
                 .default_value(cli_constants::VERIFICATION_CONTRACT_DEFAULT_PATH),
         )
+        .arg(
+            Arg::with_name("precompile")
+                .long("precompile")
+                .help("Interface of the BN254 precompiles called by the verifier: `eip197` or `custom:<add>,<mul>,<pairing>` addresses. BLS12-381 verifiers always call the EIP-2537 precompiles")
+                .value_name("VARIANT")
+                .takes_value(true)
+                .required(false)
+                .default_value("eip197"),
+        )
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -62,6 +71,9 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         (CurveParameter::Bn128, SchemeParameter::G16) => {
             cli_export_verifier::<Bn128Field, G16>(sub_matches, vk)
         }
+        (CurveParameter::Bls12_381, SchemeParameter::G16) => {
+            cli_export_verifier::<Bls12_381Field, G16>(sub_matches, vk)
+        }
         (CurveParameter::Bn128, SchemeParameter::GM17) => {
             cli_export_verifier::<Bn128Field, GM17>(sub_matches, vk)
         }
@@ -80,7 +92,10 @@ fn cli_export_verifier<T: SolidityCompatibleField, S: SolidityCompatibleScheme<T
 
     let vk = serde_json::from_value(vk).map_err(|why| format!("{}", why))?;
 
-    let verifier = S::export_solidity_verifier(vk);
+    let config = SolidityExportConfig::default()
+        .precompile(Precompile::try_from(sub_matches.value_of("precompile").unwrap())?);
+
+    let verifier = S::export_solidity_verifier(vk, &config)?;
 
     //write output file
     let output_path = Path::new(sub_matches.value_of("output").unwrap());
@@ -1,14 +1,19 @@
 use crate::cli_constants;
 use clap::{App, Arg, ArgMatches, SubCommand};
 use include_dir::{include_dir, Dir};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::fs::{self, File};
 use std::io::{BufReader, BufWriter, Write};
 use std::path::Path;
-use std::process;
+#[cfg(feature = "ark")]
+use zokrates_ark::Ark;
+#[cfg(feature = "bellman")]
+use zokrates_bellman::Bellman;
 use zokrates_common::constants;
-use zokrates_common::helpers::{CurveParameter, SchemeParameter};
-use zokrates_field::{Bls12_381Field, Bn128Field};
+use zokrates_common::helpers::{BackendParameter, CurveParameter, Parameters, SchemeParameter};
+use zokrates_field::{Bls12_381Field, Bn128Field, Bw6_761Field};
 use zokrates_proof_systems::*;
 
 pub fn subcommand() -> App<'static, 'static> {
@@ -18,9 +23,11 @@ pub fn subcommand() -> App<'static, 'static> {
             Arg::with_name("input")
                 .short("i")
                 .long("input")
-                .help("Path of the verification key")
+                .help("Path of the verification key, or of a keypair carrying one, repeat to export several verifiers at once")
                 .value_name("FILE")
                 .takes_value(true)
+                .multiple(true)
+                .number_of_values(1)
                 .required(false)
                 .default_value(cli_constants::VERIFICATION_KEY_DEFAULT_PATH),
         )
@@ -34,19 +41,262 @@ pub fn subcommand() -> App<'static, 'static> {
                 .possible_values(cli_constants::BACKENDS)
                 .default_value(constants::BELLMAN),
         )
+        .arg(
+            Arg::with_name("ledger")
+                .long("ledger")
+                .help("Path of a ledger of exported verification keys, used to warn about key reuse")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("name")
+                .long("name")
+                .help("Name under which the exported verifier is recorded in the ledger")
+                .value_name("NAME")
+                .takes_value(true)
+                .required(false)
+                .default_value("verifier"),
+        )
+        .arg(
+            Arg::with_name("g2-order")
+                .long("g2-order")
+                .help("Order of the Fq2 components of G2 points: `scrypt` is swapped on-chain by `createTwistPoint`, `standard` is imaginary-first and only supported with --key-only")
+                .value_name("ORDER")
+                .takes_value(true)
+                .required(false)
+                .possible_values(&["standard", "scrypt"])
+                .default_value("scrypt"),
+        )
+        .arg(
+            Arg::with_name("proof-encoding")
+                .long("proof-encoding")
+                .help("Order of the Fq2 components of the proof's G2 point the verifier accepts: `scrypt` as output by ZoKrates, `eip197` imaginary-first, swapped back on-chain")
+                .value_name("ENCODING")
+                .takes_value(true)
+                .required(false)
+                .possible_values(&["scrypt", "eip197"])
+                .default_value("scrypt"),
+        )
+        .arg(
+            Arg::with_name("subgroup-checks")
+                .long("subgroup-checks")
+                .help("Reject proof points outside the prime order subgroups on-chain")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("packed-gamma-abc")
+                .long("packed-gamma-abc")
+                .help("Store the input commitments as x coordinates and y parities, trading verification cost for a smaller verifier")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("domain-tag")
+                .long("domain-tag")
+                .help("Fix the first public input to this hex value, so that the verifier only accepts proofs generated for this domain and takes the remaining inputs")
+                .value_name("HEX")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("max-public-inputs")
+                .long("max-public-inputs")
+                .help("Fail if the verifier takes more public inputs than this, as its script would likely exceed the sCrypt size constraints [default: 64]")
+                .value_name("COUNT")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("pairing-lib-import")
+                .long("pairing-lib-import")
+                .help("Import `BN256` and `BN256Pairing` from a shared library module instead of inlining them, required for bw6_761 whose `BW6761` and `BW6761Pairing` are not embedded")
+                .value_name("MODULE")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("scrypt-ts-import")
+                .long("scrypt-ts-import")
+                .help("Module specifier to import `scrypt-ts` from")
+                .value_name("MODULE")
+                .takes_value(true)
+                .required(false)
+                .default_value("scrypt-ts"),
+        )
+        .arg(
+            Arg::with_name("update")
+                .long("update")
+                .help("If a verifier project already exists, only regenerate its verifier and leave its other files intact")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the directory of the verifier project [default: verifier], or with --key-only of the verifying key file or of its directory [default: .]")
+                .value_name("PATH")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("force")
+                .long("force")
+                .help("Overwrite the output if it already exists")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("key-only")
+                .long("key-only")
+                .help("Only export the verifying key data, including `millerb1a1` computed with the backend, to `verifying_key.ts` instead of a verifier project")
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("stdout")
+                .long("stdout")
+                .help("Print the verifier to stdout instead of writing any file")
+                .required(false)
+                .conflicts_with_all(&["key-only", "update", "force"]),
+        )
 }
 
+/// File marking a directory as a verifier project created by this command
+const PROJECT_MARKER: &str = ".zokrates-verifier";
+
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
-    let vk_path = Path::new(sub_matches.value_of("input").unwrap());
-    let vk_file = File::open(&vk_path)
-        .map_err(|why| format!("Could not open {}: {}", vk_path.display(), why))?;
+    let mut config = ScryptExportConfig::default()
+        .g2_order(G2Order::try_from(sub_matches.value_of("g2-order").unwrap())?)
+        .proof_encoding(ProofEncoding::try_from(
+            sub_matches.value_of("proof-encoding").unwrap(),
+        )?)
+        .subgroup_checks(sub_matches.is_present("subgroup-checks"))
+        .packed_gamma_abc(sub_matches.is_present("packed-gamma-abc"));
+    if let Some(module) = sub_matches.value_of("pairing-lib-import") {
+        config = config.pairing_lib_import(module);
+    }
+    if let Some(tag) = sub_matches.value_of("domain-tag") {
+        config = config.domain_tag(tag);
+    }
+    if let Some(max) = sub_matches.value_of("max-public-inputs") {
+        config = config.max_public_inputs(
+            max.parse::<usize>()
+                .map_err(|_| format!("Invalid maximum number of public inputs: {}", max))?,
+        );
+    }
+    config = config.scrypt_ts_import(sub_matches.value_of("scrypt-ts-import").unwrap());
 
-    // deserialize vk to JSON
-    let vk_reader = BufReader::new(vk_file);
-    let vk: serde_json::Value = serde_json::from_reader(vk_reader)
-        .map_err(|why| format!("Could not deserialize verification key: {}", why))?;
+    let vk_paths: Vec<&Path> = sub_matches.values_of("input").unwrap().map(Path::new).collect();
+    let ledger_path = sub_matches.value_of("ledger").map(Path::new);
+    let name = sub_matches.value_of("name").unwrap();
 
-    // extract curve and scheme parameters
+    // the ledger is only updated once the verifiers are written
+    let mut ledger_entries = ledger_path.map(|_| vec![]);
+
+    if sub_matches.is_present("stdout") {
+        let verifiers = export_verifiers(&vk_paths, name, ledger_entries.as_mut(), &config, None)?;
+
+        print_verifier(&verifiers, &mut std::io::stdout())?;
+    } else if sub_matches.is_present("key-only") {
+        println!("Exporting verifier...");
+
+        let backend = sub_matches.value_of("backend").unwrap();
+        let keys = export_verifiers(
+            &vk_paths,
+            name,
+            ledger_entries.as_mut(),
+            &config,
+            Some(backend),
+        )?;
+
+        write_verifying_keys(
+            Path::new(sub_matches.value_of("output").unwrap_or(".")),
+            &keys,
+            sub_matches.is_present("force"),
+        )?;
+    } else {
+        println!("Exporting verifier...");
+
+        let verifiers = export_verifiers(&vk_paths, name, ledger_entries.as_mut(), &config, None)?;
+
+        write_project(
+            Path::new(sub_matches.value_of("output").unwrap_or("verifier")),
+            &verifiers,
+            sub_matches.is_present("update"),
+            sub_matches.is_present("force"),
+        )?;
+    }
+
+    match (ledger_path, ledger_entries) {
+        (Some(ledger_path), Some(entries)) => record_in_ledger(ledger_path, &entries),
+        _ => Ok(()),
+    }
+}
+
+/// Exports the verifier of each verification key, keyed by the file stem of its path. With
+/// `key_only`, the name of a backend, only the verifying key data is exported. With
+/// `ledger_entries`, the fingerprint and name of each key are collected to be recorded.
+fn export_verifiers(
+    vk_paths: &[&Path],
+    name: &str,
+    mut ledger_entries: Option<&mut Vec<(String, String)>>,
+    config: &ScryptExportConfig,
+    key_only: Option<&str>,
+) -> Result<BTreeMap<String, ScryptVerifierSections>, String> {
+    let mut verifiers = BTreeMap::new();
+
+    for vk_path in vk_paths {
+        let key = vk_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .ok_or_else(|| format!("Invalid verification key path {}", vk_path.display()))?;
+
+        let vk_file = File::open(&vk_path)
+            .map_err(|why| format!("Could not open {}: {}", vk_path.display(), why))?;
+
+        // deserialize vk to JSON
+        let vk_reader = BufReader::new(vk_file);
+        let vk: serde_json::Value = serde_json::from_reader(vk_reader)
+            .map_err(|why| format!("Could not deserialize verification key: {}", why))?;
+        let vk = keypair_verification_key(vk);
+
+        if let Some(entries) = ledger_entries.as_mut() {
+            let name = match vk_paths.len() {
+                1 => name.to_string(),
+                _ => format!("{}/{}", name, key),
+            };
+            entries.push((ledger_key(&vk)?, name));
+        }
+
+        let verifier = match key_only {
+            Some(backend) => ScryptVerifierSections {
+                verifying_key_data: export_verifying_key(vk, backend, config)?,
+                ..ScryptVerifierSections::default()
+            },
+            None => export_verifier(vk, config)?,
+        };
+
+        if verifiers.insert(key.clone(), verifier).is_some() {
+            return Err(format!("Several verification keys are named `{}`", key));
+        }
+    }
+
+    Ok(verifiers)
+}
+
+/// Takes the verification key out of a serialized keypair, which carries the curve and
+/// scheme of the key as well, so that a keypair can be exported without extracting its key
+fn keypair_verification_key(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(mut keypair)
+            if keypair.contains_key("pk") && keypair.get("vk").map_or(false, |vk| vk.is_object()) =>
+        {
+            keypair.remove("vk").unwrap()
+        }
+        value => value,
+    }
+}
+
+/// Extracts the curve and scheme parameters of a verification key
+fn vk_parameters(vk: &serde_json::Value) -> Result<(&str, &str), String> {
     let vk_curve = vk
         .get("curve")
         .ok_or_else(|| "Field `curve` not found in verification key".to_string())?
@@ -58,70 +308,876 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         .as_str()
         .ok_or_else(|| "`scheme` should be a string".to_string())?;
 
+    Ok((vk_curve, vk_scheme))
+}
+
+fn export_verifier(
+    vk: serde_json::Value,
+    config: &ScryptExportConfig,
+) -> Result<ScryptVerifierSections, String> {
+    // extract curve and scheme parameters
+    let (vk_curve, vk_scheme) = vk_parameters(&vk)?;
+
+    check_pairing_friendly(vk_curve)?;
+    check_scrypt_compatible(vk_curve, config)?;
+
     let curve_parameter = CurveParameter::try_from(vk_curve)?;
     let scheme_parameter = SchemeParameter::try_from(vk_scheme)?;
 
     match (curve_parameter, scheme_parameter) {
         (CurveParameter::Bn128, SchemeParameter::G16) => {
-            cli_export_verifier::<Bn128Field, G16>(vk, CurveParameter::Bn128)
+            cli_export_verifier::<Bn128Field, G16>(vk, CurveParameter::Bn128, config)
         }
         (CurveParameter::Bn128, SchemeParameter::GM17) => {
-            cli_export_verifier::<Bn128Field, GM17>(vk, CurveParameter::Bn128)
+            cli_export_verifier::<Bn128Field, GM17>(vk, CurveParameter::Bn128, config)
         }
         (CurveParameter::Bn128, SchemeParameter::MARLIN) => {
-            cli_export_verifier::<Bn128Field, Marlin>(vk, CurveParameter::Bn128)
+            cli_export_verifier::<Bn128Field, Marlin>(vk, CurveParameter::Bn128, config)
         }
         (CurveParameter::Bls12_381, SchemeParameter::G16) => {
-            cli_export_verifier::<Bls12_381Field, G16>(vk, CurveParameter::Bls12_381)
+            cli_export_verifier::<Bls12_381Field, G16>(vk, CurveParameter::Bls12_381, config)
+        }
+        (CurveParameter::Bw6_761, SchemeParameter::G16) => {
+            cli_export_verifier::<Bw6_761Field, G16>(vk, CurveParameter::Bw6_761, config)
         }
         (curve_parameter, scheme_parameter) => Err(format!("Could not export verifier with given parameters (curve: {}, scheme: {}): not supported", curve_parameter, scheme_parameter))
     }
 }
 
+fn export_verifying_key(
+    vk: serde_json::Value,
+    backend: &str,
+    config: &ScryptExportConfig,
+) -> Result<String, String> {
+    let (vk_curve, vk_scheme) = vk_parameters(&vk)?;
+
+    check_pairing_friendly(vk_curve)?;
+    check_scrypt_compatible(vk_curve, config)?;
+
+    let parameters = Parameters::try_from((backend, vk_curve, vk_scheme))?;
+
+    match parameters {
+        #[cfg(feature = "bellman")]
+        Parameters(BackendParameter::Bellman, CurveParameter::Bn128, SchemeParameter::G16) => {
+            cli_export_verifying_key::<Bn128Field, G16, Bellman>(vk, CurveParameter::Bn128, config)
+        }
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, CurveParameter::Bn128, SchemeParameter::G16) => {
+            cli_export_verifying_key::<Bn128Field, G16, Ark>(vk, CurveParameter::Bn128, config)
+        }
+        Parameters(_, curve_parameter, scheme_parameter) => Err(format!("Could not export verifying key with given parameters (curve: {}, scheme: {}): not supported", curve_parameter, scheme_parameter))
+    }
+}
+
+/// Rejects curves without a pairing, which cannot have a SNARK verifier
+fn check_pairing_friendly(curve: &str) -> Result<(), String> {
+    match curve {
+        "secp256k1" => Err(
+            "secp256k1 is not pairing-friendly; use the Pedersen key-proof path instead"
+                .to_string(),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Curves the sCrypt verifiers can be templated for, i.e. those implementing
+/// `ScryptCompatibleField`
+const SCRYPT_CURVES: &[&str] = &[constants::BN128, constants::BLS12_381, constants::BW6_761];
+
+/// Curves without an embedded sCrypt pairing library, whose verifiers can only be exported
+/// against one imported with `--pairing-lib-import`
+const IMPORTED_PAIRING_LIB_CURVES: &[&str] = &[constants::BW6_761];
+
+/// Rejects curves the sCrypt verifiers cannot be templated for, or not without a pairing
+/// library to import, before dispatching on them
+fn check_scrypt_compatible(curve: &str, config: &ScryptExportConfig) -> Result<(), String> {
+    if !SCRYPT_CURVES.contains(&curve) {
+        return Err(format!(
+            "curve {} is not sCrypt-compatible, expected one of {}",
+            curve,
+            SCRYPT_CURVES.join(", ")
+        ));
+    }
+    if IMPORTED_PAIRING_LIB_CURVES.contains(&curve) && config.pairing_lib_import.is_none() {
+        return Err(format!(
+            "No sCrypt pairing library is embedded for {}, use --pairing-lib-import to import one",
+            curve
+        ));
+    }
+    Ok(())
+}
+
+/// Maps the hash of a verification key to the name it was last exported under
+type Ledger = BTreeMap<String, String>;
+
+/// Fingerprints a verification key, so that the same g16 key is recognized whatever its
+/// encoding. Keys of other schemes have no canonical encoding of their constants, so their
+/// fields are hashed as written
+fn ledger_key(vk: &serde_json::Value) -> Result<String, String> {
+    if let Some(scheme) = vk.get("scheme").and_then(|s| s.as_str()) {
+        if scheme != "g16" {
+            return Ok(hex::encode(Sha256::digest(vk.to_string().as_bytes())));
+        }
+    }
+
+    let vk: groth16::VerificationKey<G1Affine, G2Affine> = serde_json::from_value(vk.clone())
+        .map_err(|why| {
+            format!(
+                "Could not fingerprint verification key for the ledger, only g16 keys are supported: {}",
+                why
+            )
+        })?;
+    Ok(vk.fingerprint())
+}
+
+/// Records `name` for `key`, returning the previous name if the key was exported under another one
+fn check_ledger(ledger: &mut Ledger, key: String, name: &str) -> Option<String> {
+    match ledger.insert(key, name.to_string()) {
+        Some(previous) if previous != name => Some(previous),
+        _ => None,
+    }
+}
+
+/// Records the fingerprint and name of each exported key in the ledger at `path`, warning
+/// about keys previously exported under another name
+fn record_in_ledger(path: &Path, entries: &[(String, String)]) -> Result<(), String> {
+    let mut ledger: Ledger = match File::open(path) {
+        Ok(file) => serde_json::from_reader(BufReader::new(file))
+            .map_err(|why| format!("Could not deserialize ledger {}: {}", path.display(), why))?,
+        Err(_) => Ledger::new(),
+    };
+
+    for (key, name) in entries {
+        if let Some(previous) = check_ledger(&mut ledger, key.clone(), name) {
+            // on stderr, so as not to mix with a verifier printed with `--stdout`
+            eprintln!(
+                "WARNING: this verification key was already exported as `{}`, exporting it as `{}` may indicate key reuse",
+                previous, name
+            );
+        }
+    }
+
+    let file = File::create(path)
+        .map_err(|why| format!("Could not create {}: {}", path.display(), why))?;
+    serde_json::to_writer_pretty(BufWriter::new(file), &ledger)
+        .map_err(|why| format!("Could not write ledger {}: {}", path.display(), why))?;
+
+    Ok(())
+}
+
 fn cli_export_verifier<T: ScryptCompatibleField, S: ScryptCompatibleScheme<T>>(
     vk: serde_json::Value,
     curve_parameter: CurveParameter,
+    config: &ScryptExportConfig,
+) -> Result<ScryptVerifierSections, String> {
+    let vk = tagged_verification_key::<T, S>(vk)?;
+
+    S::export_scrypt_verifier_sections(vk, curve_parameter, config)
+}
+
+/// Reads a verification key through its tagged form, rejecting one whose curve and scheme
+/// tags differ from the parameters the export was dispatched on
+fn tagged_verification_key<T: ScryptCompatibleField, S: ScryptCompatibleScheme<T>>(
+    vk: serde_json::Value,
+) -> Result<S::VerificationKey, String> {
+    let vk: TaggedVerificationKey<T, S> =
+        serde_json::from_value(vk).map_err(|why| format!("{}", why))?;
+    vk.check_tags()?;
+    Ok(vk.vk)
+}
+
+fn cli_export_verifying_key<
+    T: ScryptCompatibleField,
+    S: ScryptCompatibleScheme<T>,
+    B: Backend<T, S>,
+>(
+    vk: serde_json::Value,
+    curve_parameter: CurveParameter,
+    config: &ScryptExportConfig,
+) -> Result<String, String> {
+    // the miller loop and the export each consume a verification key
+    let miller_vk = tagged_verification_key::<T, S>(vk.clone())?;
+    let vk = tagged_verification_key::<T, S>(vk)?;
+
+    let miller_beta_alpha = B::get_miller_beta_alpha_string(miller_vk);
+
+    S::export_verifying_key_json(vk, curve_parameter, config, &miller_beta_alpha)
+}
+
+/// Writes the verifying key data to `verifying_key.ts` under the directory `output`, or to
+/// `verifying_key_<key>.ts` each if there are several. A single key is written to `output`
+/// itself unless it is a directory. Existing files are only overwritten with `force`.
+fn write_verifying_keys(
+    output: &Path,
+    keys: &BTreeMap<String, ScryptVerifierSections>,
+    force: bool,
 ) -> Result<(), String> {
-    println!("Exporting verifier...");
+    let output_paths = keys
+        .keys()
+        .map(|key| match (keys.len(), output.is_dir()) {
+            (1, false) => output.to_path_buf(),
+            (1, true) => output.join("verifying_key.ts"),
+            _ => output.join(format!("verifying_key_{}.ts", key)),
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(existing) = output_paths.iter().find(|path| path.exists()) {
+        if !force {
+            return Err(format!(
+                "Output file {} already exists, use --force to overwrite it",
+                existing.display()
+            ));
+        }
+    }
+    if keys.len() > 1 {
+        fs::create_dir_all(output)
+            .map_err(|why| format!("Could not create {}: {}", output.display(), why))?;
+    }
 
-    let vk = serde_json::from_value(vk).map_err(|why| format!("{}", why))?;
+    for (sections, output_path) in keys.values().zip(output_paths) {
+        fs::write(
+            &output_path,
+            format!(
+                "export const VERIFYING_KEY_DATA = {}\n",
+                sections.verifying_key_data
+            ),
+        )
+        .map_err(|why| format!("Could not write {}: {}", output_path.display(), why))?;
 
-    let verifier = S::export_scrypt_verifier(vk, curve_parameter);
+        println!("Verifying key data written to '{}'", output_path.display());
+    }
 
-    static PROJECT_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR");
-    let scrypt_proj_template = PROJECT_DIR.get_dir("scrypt_proj_template/").unwrap();
+    Ok(())
+}
 
-    if let Err(e) = fs::remove_dir_all("scrypt_proj_template") {
-        println!("Project template not present.");
+/// Prints the single verifier in `verifiers`, as it would be written to `snark.ts`
+fn print_verifier<W: Write>(
+    verifiers: &BTreeMap<String, ScryptVerifierSections>,
+    out: &mut W,
+) -> Result<(), String> {
+    match verifiers.values().collect::<Vec<_>>().as_slice() {
+        [verifier] => verifier
+            .write_to(out)
+            .map_err(|why| format!("Could not write verifier: {}", why)),
+        _ => Err("Only a single verifier can be printed to stdout".to_string()),
     }
+}
 
-    if let Err(e) = fs::remove_dir_all("verifier") {
-        println!("Verifier dir not present.");
+/// Writes the verifiers into the project scaffolding at `project_dir`: a single verifier goes
+/// to `snark.ts`, several verifiers go to `snark_<key>.ts` each. Verifiers in the sCrypt
+/// language are compiled on their own, so they are written to `snark.scrypt` without any
+/// scaffolding. An existing directory is only replaced with `force`, or kept with all its
+/// other files with `update` if it is a project.
+fn write_project(
+    project_dir: &Path,
+    verifiers: &BTreeMap<String, ScryptVerifierSections>,
+    update: bool,
+    force: bool,
+) -> Result<(), String> {
+    let language = project_language(verifiers)?;
+
+    if update && project_dir.join(PROJECT_MARKER).is_file() {
+        write_verifiers(project_dir, verifiers)?;
+
+        println!("Verifier code updated in '{}' dir.", project_dir.display());
+        return Ok(());
     }
 
-    if let Err(e) = fs::create_dir("scrypt_proj_template") {
-        eprintln!("Failed to create empty verifier dir: {e}");
-        process::exit(1);
+    if project_dir.exists() {
+        if !force {
+            return Err(format!(
+                "Output directory {} already exists, use --force to overwrite it",
+                project_dir.display()
+            ));
+        }
+        fs::remove_dir_all(project_dir)
+            .map_err(|why| format!("Could not remove {}: {}", project_dir.display(), why))?;
     }
-    if let Err(e) = scrypt_proj_template.extract("") {
-        eprintln!("Failed extracting verifier dir: {e}");
-        process::exit(1);
+
+    if language == ScryptLanguage::Scrypt {
+        fs::create_dir_all(project_dir)
+            .map_err(|why| format!("Failed to create verifier dir: {}", why))?;
+        write_verifiers(project_dir, verifiers)?;
+
+        println!(
+            "Verifier contract exported to '{}' dir.",
+            project_dir.display()
+        );
+        return Ok(());
     }
-    if let Err(e) = fs::rename("scrypt_proj_template", "verifier") {
-        eprintln!("Failed to rename verifier dir: {e}");
-        process::exit(1);
+
+    static PROJECT_DIR: Dir<'_> = include_dir!("$CARGO_MANIFEST_DIR");
+    let scrypt_proj_template = PROJECT_DIR.get_dir("scrypt_proj_template/").unwrap();
+
+    // the template is extracted into a temporary directory next to the project directory,
+    // then renamed to it, leaving any other directory untouched
+    let parent = match project_dir.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(parent)
+        .map_err(|why| format!("Could not create {}: {}", parent.display(), why))?;
+    let extract_dir = tempfile::tempdir_in(parent)
+        .map_err(|why| format!("Failed to create empty verifier dir: {}", why))?;
+    scrypt_proj_template
+        .extract(extract_dir.path())
+        .map_err(|why| format!("Failed extracting verifier dir: {}", why))?;
+    fs::rename(
+        extract_dir.path().join(scrypt_proj_template.path()),
+        project_dir,
+    )
+    .map_err(|why| format!("Failed to rename verifier dir: {}", why))?;
+
+    write_verifiers(project_dir, verifiers)?;
+
+    println!(
+        "Verifier code along with scaffolding exported to '{}' dir.",
+        project_dir.display()
+    );
+    println!(
+        "Initialize the repo: cd {} && git init && npm i",
+        project_dir.display()
+    );
+    Ok(())
+}
+
+/// Language of the verifiers, which must all be written in the same one to share a project
+fn project_language(
+    verifiers: &BTreeMap<String, ScryptVerifierSections>,
+) -> Result<ScryptLanguage, String> {
+    let mut languages = verifiers.values().map(|verifier| verifier.language);
+    let language = languages.next().unwrap_or_default();
+    match languages.all(|l| l == language) {
+        true => Ok(language),
+        false => Err(
+            "Verifiers in the sCrypt language and in scrypt-ts cannot be exported to the same project"
+                .to_string(),
+        ),
     }
+}
 
+fn write_verifiers(
+    project_dir: &Path,
+    verifiers: &BTreeMap<String, ScryptVerifierSections>,
+) -> Result<(), String> {
     // Write output files
-    let output_path = Path::new("verifier/src/contracts/snark.ts");
-    let output_file = File::create(&output_path)
-        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
-    let mut writer = BufWriter::new(output_file);
-    writer
-        .write_all(verifier.as_bytes())
-        .map_err(|_| "Failed writing output to file".to_string())?;
-
-    println!("Verifier code along with scaffolding exported to 'verifier' dir.");
-    println!("Initialize the repo: cd verifier && git init && npm i");
+    for (key, verifier) in verifiers {
+        let extension = verifier.language.file_extension();
+        let file_name = match verifiers.len() {
+            1 => format!("snark.{}", extension),
+            _ => format!("snark_{}.{}", key, extension),
+        };
+        let output_path = match verifier.language {
+            ScryptLanguage::ScryptTs => project_dir.join("src/contracts").join(file_name),
+            ScryptLanguage::Scrypt => project_dir.join(file_name),
+        };
+        let output_file = File::create(&output_path)
+            .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+        let mut writer = BufWriter::new(output_file);
+        verifier
+            .write_to(&mut writer)
+            .and_then(|_| writer.flush())
+            .map_err(|_| "Failed writing output to file".to_string())?;
+    }
+
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secp256k1_is_rejected_with_guidance() {
+        assert_eq!(
+            check_pairing_friendly("secp256k1").unwrap_err(),
+            "secp256k1 is not pairing-friendly; use the Pedersen key-proof path instead"
+        );
+        assert!(check_pairing_friendly("bn128").is_ok());
+    }
+
+    #[test]
+    fn mismatched_tags_are_rejected() {
+        assert!(tagged_verification_key::<Bn128Field, G16>(vk_json()).is_ok());
+
+        let mut vk = vk_json();
+        vk["curve"] = serde_json::json!("bls12_381");
+        assert_eq!(
+            cli_export_verifier::<Bn128Field, G16>(
+                vk,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default()
+            )
+            .map(|_| ())
+            .unwrap_err(),
+            "Verification key is tagged with curve bls12_381 and scheme g16, expected curve bn128 and scheme g16"
+        );
+    }
+
+    #[test]
+    fn unsupported_curve_is_rejected_early() {
+        let config = ScryptExportConfig::default();
+        assert_eq!(
+            check_scrypt_compatible("bls12_377", &config).unwrap_err(),
+            "curve bls12_377 is not sCrypt-compatible, expected one of bn128, bls12_381, bw6_761"
+        );
+
+        // bw6_761 verifiers need a pairing library to import
+        assert_eq!(
+            check_scrypt_compatible("bw6_761", &config).unwrap_err(),
+            "No sCrypt pairing library is embedded for bw6_761, use --pairing-lib-import to import one"
+        );
+        assert!(check_scrypt_compatible("bw6_761", &config.pairing_lib_import("./bw6761")).is_ok());
+
+        let mut vk = vk_json();
+        vk["curve"] = serde_json::json!("bls12_377");
+        assert_eq!(
+            export_verifier(vk, &ScryptExportConfig::default())
+                .map(|_| ())
+                .unwrap_err(),
+            "curve bls12_377 is not sCrypt-compatible, expected one of bn128, bls12_381, bw6_761"
+        );
+    }
+
+    fn vk_json() -> serde_json::Value {
+        let g1 = serde_json::json!(["0x1", "0x2"]);
+        let g2 = serde_json::json!([
+            [
+                "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed",
+                "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2"
+            ],
+            [
+                "0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+                "0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b"
+            ]
+        ]);
+        serde_json::json!({
+            "scheme": "g16",
+            "curve": "bn128",
+            "alpha": g1,
+            "beta": g2,
+            "gamma": g2,
+            "delta": g2,
+            "gamma_abc": [g1, g1]
+        })
+    }
+
+    #[test]
+    fn export_several_verifiers() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let paths: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|name| {
+                let path = dir.path().join(format!("{}.key", name));
+                serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+                path
+            })
+            .collect();
+        let paths: Vec<&Path> = paths.iter().map(|p| p.as_path()).collect();
+
+        let verifiers = export_verifiers(
+            &paths,
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(verifiers.keys().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+        for verifier in verifiers.values() {
+            assert!(verifier.join().contains("export const N_PUB_INPUTS = 1"));
+        }
+    }
+
+    #[test]
+    fn export_from_keypair() {
+        let vk = serde_json::from_value(vk_json()).unwrap();
+        let keypair = TaggedKeypair::<Bn128Field, G16>::new(SetupKeypair::new(vk, vec![1, 2, 3]));
+
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("keypair.json");
+        serde_json::to_writer(File::create(&path).unwrap(), &keypair).unwrap();
+
+        let verifiers = export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            None,
+        )
+        .unwrap();
+
+        assert!(verifiers["keypair"].join().contains("export const N_PUB_INPUTS = 1"));
+        assert_eq!(keypair_verification_key(vk_json()), vk_json());
+    }
+
+    #[cfg(feature = "bellman")]
+    #[test]
+    fn export_verifying_key_only() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+
+        let keys = export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            Some(constants::BELLMAN),
+        )
+        .unwrap();
+
+        let key = &keys["verification"].verifying_key_data;
+        assert!(key.contains("millerb1a1: {"));
+        assert!(!key.contains("SmartContractLib"));
+
+        write_verifying_keys(dir.path(), &keys, false).unwrap();
+        assert!(fs::read_to_string(dir.path().join("verifying_key.ts"))
+            .unwrap()
+            .starts_with("export const VERIFYING_KEY_DATA = {"));
+
+        // an existing file is only overwritten with `--force`
+        let file = dir.path().join("key.ts");
+        write_verifying_keys(&file, &keys, false).unwrap();
+        assert!(write_verifying_keys(&file, &keys, false)
+            .unwrap_err()
+            .contains("already exists, use --force"));
+        assert!(write_verifying_keys(dir.path(), &keys, false).is_err());
+        write_verifying_keys(&file, &keys, true).unwrap();
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            fs::read_to_string(dir.path().join("verifying_key.ts")).unwrap()
+        );
+
+        // several keys are written to a directory
+        let mut several = keys.clone();
+        several.insert("other".to_string(), keys["verification"].clone());
+        let several_dir = dir.path().join("keys");
+        write_verifying_keys(&several_dir, &several, false).unwrap();
+        assert!(several_dir.join("verifying_key_other.ts").is_file());
+        assert!(several_dir.join("verifying_key_verification.ts").is_file());
+    }
+
+    #[cfg(all(feature = "bellman", feature = "ark"))]
+    #[test]
+    fn backends_agree_on_millerb1a1() {
+        let vk: groth16::VerificationKey<G1Affine, G2Affine> =
+            serde_json::from_value(vk_json()).unwrap();
+        let bellman =
+            <Bellman as Backend<Bn128Field, G16>>::get_miller_beta_alpha_string(vk.clone());
+        let ark = <Ark as Backend<Bn128Field, G16>>::get_miller_beta_alpha_string(vk);
+        assert_eq!(bellman, ark);
+
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+        let export = |backend| {
+            export_verifiers(
+                &[path.as_path()],
+                "verifier",
+                None,
+                &ScryptExportConfig::default(),
+                Some(backend),
+            )
+            .unwrap()
+        };
+        assert_eq!(export(constants::BELLMAN), export(constants::ARK));
+    }
+
+    /// Checks that `text` holds `count` decimal `n`-suffixed literals, all bn128 field elements
+    #[cfg(feature = "ark")]
+    fn check_bn128_literals(text: &str, count: usize) {
+        const BN128_P: &str =
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+
+        let literals: Vec<_> = regex::Regex::new(r"\b(\d+)n\b")
+            .unwrap()
+            .captures_iter(text)
+            .map(|c| c[1].to_string())
+            .collect();
+        assert_eq!(literals.len(), count, "{}", text);
+        for literal in literals {
+            assert!(literal.len() <= BN128_P.len(), "{}", literal);
+            assert!(format!("{:0>77}", literal).as_str() < BN128_P, "{}", literal);
+        }
+    }
+
+    #[cfg(feature = "ark")]
+    #[test]
+    fn ark_proof_round_trip() {
+        use zokrates_ast::flat::{Parameter, Variable};
+        use zokrates_ast::ir::{Prog, Statement};
+        use zokrates_interpreter::Interpreter;
+
+        // a single public input, returned as is
+        let program: Prog<Bn128Field> = Prog {
+            arguments: vec![Parameter::public(Variable::new(0))],
+            return_count: 1,
+            statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+        };
+        let keypair = Ark::setup_with_seed(program.clone(), [5; 32]);
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[Bn128Field::from(42)])
+            .unwrap();
+        let proof = Ark::generate_proof_with_seed(program, witness, keypair.pk, [6; 32]);
+        assert!(<Ark as Backend<Bn128Field, G16>>::verify(
+            keypair.vk.clone(),
+            Proof::new(proof.proof.clone(), proof.inputs.clone())
+        ));
+
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        serde_json::to_writer(
+            File::create(&path).unwrap(),
+            &TaggedVerificationKey::<Bn128Field, G16>::new(keypair.vk),
+        )
+        .unwrap();
+
+        let verifiers = export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            None,
+        )
+        .unwrap();
+        assert!(verifiers["verification"].join().contains(&format!(
+            "export const N_PUB_INPUTS = {}",
+            proof.inputs.len()
+        )));
+
+        let keys = export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            Some(constants::ARK),
+        )
+        .unwrap();
+        let key = &keys["verification"].verifying_key_data;
+        // alpha, beta, gamma, delta and two gammaAbc points, then the 12 limbs of millerb1a1
+        check_bn128_literals(key, 2 + 3 * 4 + 2 * 2 + 12);
+        let miller = &key[key.find("millerb1a1:").unwrap()..];
+        check_bn128_literals(miller, 12);
+
+        let proof_text = [
+            proof.proof.a.to_scrypt_string().unwrap(),
+            proof.proof.b.to_scrypt_string().unwrap(),
+            proof.proof.c.to_scrypt_string().unwrap(),
+        ]
+        .join(",");
+        check_bn128_literals(&proof_text, 2 + 4 + 2);
+    }
+
+    #[test]
+    fn print_verifier_to_stdout() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+
+        let verifiers = export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            None,
+            &ScryptExportConfig::default(),
+            None,
+        )
+        .unwrap();
+
+        let mut out = vec![];
+        print_verifier(&verifiers, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), verifiers["verification"].join());
+        assert!(verifiers["verification"]
+            .join()
+            .contains("export const N_PUB_INPUTS = 1"));
+
+        // nothing but the verification key is on disk
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let mut several = verifiers.clone();
+        several.insert("other".to_string(), ScryptVerifierSections::default());
+        assert!(print_verifier(&several, &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn print_verifier_with_ledger() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        let ledger_path = dir.path().join("ledger.json");
+        serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+
+        for name in ["verifierA", "verifierB"] {
+            let mut entries = vec![];
+            let verifiers = export_verifiers(
+                &[path.as_path()],
+                name,
+                Some(&mut entries),
+                &ScryptExportConfig::default(),
+                None,
+            )
+            .unwrap();
+
+            let mut out = vec![];
+            print_verifier(&verifiers, &mut out).unwrap();
+            record_in_ledger(&ledger_path, &entries).unwrap();
+
+            // the reuse warning is not part of the printed verifier
+            assert_eq!(
+                String::from_utf8(out).unwrap(),
+                verifiers["verification"].join()
+            );
+        }
+
+        let ledger: Ledger = serde_json::from_reader(File::open(&ledger_path).unwrap()).unwrap();
+        assert_eq!(
+            ledger.get(&ledger_key(&vk_json()).unwrap()).unwrap(),
+            "verifierB"
+        );
+    }
+
+    #[test]
+    fn ledger_warns_on_key_reuse() {
+        let vk = vk_json();
+        let key = ledger_key(&vk).unwrap();
+
+        let mut ledger = Ledger::new();
+        assert_eq!(check_ledger(&mut ledger, key.clone(), "verifierA"), None);
+        assert_eq!(check_ledger(&mut ledger, key.clone(), "verifierA"), None);
+        assert_eq!(
+            check_ledger(&mut ledger, key, "verifierB"),
+            Some("verifierA".to_string())
+        );
+    }
+
+    #[test]
+    fn ledger_key_ignores_hex_encoding() {
+        let mut vk = vk_json();
+        vk["alpha"] = serde_json::json!([
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "0x02"
+        ]);
+        assert_eq!(ledger_key(&vk).unwrap(), ledger_key(&vk_json()).unwrap());
+
+        vk["alpha"] = serde_json::json!(["0x2", "0x1"]);
+        assert_ne!(ledger_key(&vk).unwrap(), ledger_key(&vk_json()).unwrap());
+    }
+
+    #[test]
+    fn ledger_key_hashes_other_schemes_as_written() {
+        let mut vk = vk_json();
+        vk["scheme"] = serde_json::json!("gm17");
+        vk["h"] = vk["beta"].clone();
+        let key = ledger_key(&vk).unwrap();
+        assert_ne!(key, ledger_key(&vk_json()).unwrap());
+
+        vk["h"][0][0] = serde_json::json!("0x2");
+        assert_ne!(ledger_key(&vk).unwrap(), key);
+    }
+
+    #[test]
+    fn ledger_is_recorded_after_export() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let path = dir.path().join("verification.key");
+        let ledger_path = dir.path().join("ledger.json");
+
+        serde_json::to_writer(File::create(&path).unwrap(), &vk_json()).unwrap();
+        let mut entries = vec![];
+        export_verifiers(
+            &[path.as_path()],
+            "verifier",
+            Some(&mut entries),
+            &ScryptExportConfig::default(),
+            None,
+        )
+        .unwrap();
+        record_in_ledger(&ledger_path, &entries).unwrap();
+
+        let ledger: Ledger = serde_json::from_reader(File::open(&ledger_path).unwrap()).unwrap();
+        assert_eq!(
+            ledger.get(&ledger_key(&vk_json()).unwrap()).unwrap(),
+            "verifier"
+        );
+    }
+
+    #[test]
+    fn update_keeps_project_files() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let project = dir.path().join("out/verifierA");
+        let snark = project.join("src/contracts/snark.ts");
+        let package = project.join("package.json");
+        let custom = project.join("tests/custom.test.ts");
+
+        let verifiers = |content: &str| {
+            let mut verifiers = BTreeMap::new();
+            verifiers.insert(
+                "verification".to_string(),
+                ScryptVerifierSections {
+                    snark_class: content.to_string(),
+                    ..ScryptVerifierSections::default()
+                },
+            );
+            verifiers
+        };
+
+        // a directory named after the template next to the project is left untouched
+        let sibling = dir.path().join("out/scrypt_proj_template");
+        fs::create_dir_all(&sibling).unwrap();
+        fs::write(sibling.join("keep"), "keep").unwrap();
+
+        write_project(&project, &verifiers("first"), false, false).unwrap();
+        assert!(!dir.path().join("verifier").exists());
+        assert_eq!(fs::read_to_string(sibling.join("keep")).unwrap(), "keep");
+        assert_eq!(fs::read_dir(dir.path().join("out")).unwrap().count(), 2);
+        assert!(project.join(PROJECT_MARKER).is_file());
+        assert_eq!(fs::read_to_string(&snark).unwrap(), "first\n");
+
+        fs::write(&package, "{}").unwrap();
+        fs::write(&custom, "custom").unwrap();
+
+        write_project(&project, &verifiers("second"), true, false).unwrap();
+        assert_eq!(fs::read_to_string(&snark).unwrap(), "second\n");
+        assert_eq!(fs::read_to_string(&package).unwrap(), "{}");
+        assert_eq!(fs::read_to_string(&custom).unwrap(), "custom");
+
+        // without `--update` an existing project is only replaced with `--force`
+        assert!(write_project(&project, &verifiers("third"), false, false)
+            .unwrap_err()
+            .contains("already exists"));
+        assert_eq!(fs::read_to_string(&snark).unwrap(), "second\n");
+
+        write_project(&project, &verifiers("third"), false, true).unwrap();
+        assert_eq!(fs::read_to_string(&snark).unwrap(), "third\n");
+        assert!(!custom.exists());
+    }
+
+    #[test]
+    fn scrypt_language_verifier_has_no_scaffolding() {
+        let dir = tempdir::TempDir::new("export_verifier_scrypt").unwrap();
+        let project = dir.path().join("verifier");
+
+        let mut verifiers = BTreeMap::new();
+        verifiers.insert(
+            "verification".to_string(),
+            ScryptVerifierSections {
+                snark_class: "contract Verifier {}".to_string(),
+                language: ScryptLanguage::Scrypt,
+                ..ScryptVerifierSections::default()
+            },
+        );
+
+        write_project(&project, &verifiers, false, false).unwrap();
+        assert_eq!(
+            fs::read_to_string(project.join("snark.scrypt")).unwrap(),
+            "contract Verifier {}\n"
+        );
+        assert_eq!(fs::read_dir(&project).unwrap().count(), 1);
+
+        // verifiers of both languages cannot share a project
+        verifiers.insert("other".to_string(), ScryptVerifierSections::default());
+        assert!(write_project(&project, &verifiers, false, true)
+            .unwrap_err()
+            .contains("cannot be exported to the same project"));
+        assert!(project.join("snark.scrypt").is_file());
+    }
+}
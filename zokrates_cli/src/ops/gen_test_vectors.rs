@@ -0,0 +1,148 @@
+use crate::cli_constants;
+use clap::{App, AppSettings, Arg, ArgMatches, SubCommand};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zokrates_ark::Ark;
+use zokrates_ast::flat::{Parameter, Variable};
+use zokrates_ast::ir::{Prog, Statement};
+use zokrates_common::constants;
+use zokrates_common::helpers::*;
+use zokrates_field::{
+    ArkFieldExtensions, Bls12_377Field, Bls12_381Field, Bn128Field, Bw6_761Field, Field,
+};
+use zokrates_interpreter::Interpreter;
+use zokrates_proof_systems::*;
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("gen-test-vectors")
+        .about("Generates deterministic test vectors from a seed")
+        .setting(AppSettings::Hidden)
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .help("Hex encoded 32 byte seed of the setup and proof randomness")
+                .value_name("SEED")
+                .takes_value(true)
+                .required(true),
+        )
+        .arg(
+            Arg::with_name("curve")
+                .short("c")
+                .long("curve")
+                .help("Curve of the test vectors")
+                .takes_value(true)
+                .required(false)
+                .possible_values(cli_constants::CURVES)
+                .default_value(constants::BN128),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Directory of the generated fixtures")
+                .value_name("DIR")
+                .takes_value(true)
+                .required(false)
+                .default_value("fixtures"),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let seed = super::setup::parse_seed(sub_matches.value_of("seed").unwrap())?;
+    let dir = Path::new(sub_matches.value_of("output").unwrap());
+
+    let path = match CurveParameter::try_from(sub_matches.value_of("curve").unwrap())? {
+        CurveParameter::Bn128 => gen_test_vectors::<Bn128Field>(seed, dir),
+        CurveParameter::Bls12_381 => gen_test_vectors::<Bls12_381Field>(seed, dir),
+        CurveParameter::Bls12_377 => gen_test_vectors::<Bls12_377Field>(seed, dir),
+        CurveParameter::Bw6_761 => gen_test_vectors::<Bw6_761Field>(seed, dir),
+    }?;
+
+    println!("Test vectors written to '{}'", path.display());
+    Ok(())
+}
+
+/// Writes a g16 verification key together with a valid proof and a proof of another
+/// statement, each with the verdict of the ark verifier, to `<dir>/<curve>_g16.json`.
+/// Everything is derived from `seed`, so the same seed always yields the same file.
+fn gen_test_vectors<T: Field + ArkFieldExtensions>(
+    seed: [u8; 32],
+    dir: &Path,
+) -> Result<std::path::PathBuf, String> {
+    // a single public input, returned as is
+    let program: Prog<T> = Prog {
+        arguments: vec![Parameter::public(Variable::new(0))],
+        return_count: 1,
+        statements: vec![Statement::constraint(Variable::new(0), Variable::public(0))],
+    };
+
+    let keypair = Ark::setup_with_seed(program.clone(), seed);
+
+    // the proofs draw from their own seed, so that they do not reuse the setup randomness
+    let mut proof_seed = [0u8; 32];
+    proof_seed.copy_from_slice(&Sha256::digest(&seed));
+
+    let prove = |input: u32| {
+        let witness = Interpreter::default()
+            .execute(program.clone(), &[T::from(input)])
+            .unwrap();
+        Ark::generate_proof_with_seed(program.clone(), witness, keypair.pk.clone(), proof_seed)
+    };
+
+    let valid = prove(42);
+    // a proof of 43 claimed against the public input 42
+    let invalid = Proof::<T, G16>::new(prove(43).proof, valid.inputs.clone());
+
+    let vectors = vec![valid, invalid]
+        .into_iter()
+        .map(|proof| {
+            let verdict = <Ark as Backend<T, G16>>::verify(
+                keypair.vk.clone(),
+                Proof::new(proof.proof.clone(), proof.inputs.clone()),
+            );
+            serde_json::json!({
+                "proof": TaggedProof::<T, G16>::new(proof.proof, proof.inputs),
+                "verdict": verdict,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let fixture = serde_json::json!({
+        "vk": TaggedVerificationKey::<T, G16>::new(keypair.vk),
+        "vectors": vectors,
+    });
+
+    std::fs::create_dir_all(dir)
+        .map_err(|why| format!("Could not create {}: {}", dir.display(), why))?;
+    let path = dir.join(format!("{}_g16.json", T::name()));
+    let mut file = File::create(&path)
+        .map_err(|why| format!("Could not create {}: {}", path.display(), why))?;
+    file.write_all(serde_json::to_string_pretty(&fixture).unwrap().as_bytes())
+        .map_err(|why| format!("Could not write to {}: {}", path.display(), why))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_same_fixtures() {
+        let first_dir = tempdir::TempDir::new("gen_test_vectors").unwrap();
+        let second_dir = tempdir::TempDir::new("gen_test_vectors").unwrap();
+
+        let first = gen_test_vectors::<Bn128Field>([3; 32], first_dir.path()).unwrap();
+        let second = gen_test_vectors::<Bn128Field>([3; 32], second_dir.path()).unwrap();
+
+        let first = std::fs::read_to_string(first).unwrap();
+        assert_eq!(first, std::fs::read_to_string(second).unwrap());
+
+        let fixture: serde_json::Value = serde_json::from_str(&first).unwrap();
+        assert_eq!(fixture["vectors"][0]["verdict"], true);
+        assert_eq!(fixture["vectors"][1]["verdict"], false);
+    }
+}
@@ -11,6 +11,8 @@ use zokrates_ast::ir::{self, ProgEnum};
 use zokrates_bellman::Bellman;
 use zokrates_common::constants;
 use zokrates_common::helpers::*;
+#[cfg(feature = "ark")]
+use zokrates_field::ArkFieldExtensions;
 use zokrates_field::Field;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 use zokrates_proof_systems::*;
@@ -79,6 +81,20 @@ pub fn subcommand() -> App<'static, 'static> {
                 .possible_values(cli_constants::SCHEMES)
                 .default_value(constants::G16),
         )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .help("Hex encoded 32 byte seed of the proof randomness, for reproducible proofs (ark backend and g16 scheme only)")
+                .value_name("SEED")
+                .takes_value(true)
+                .required(false),
+        )
+        .arg(
+            Arg::with_name("unchecked-proving-key")
+                .long("unchecked-proving-key")
+                .help("Skip checking that the points of the proving key are valid (ark backend and g16 scheme only), which is faster for large keys but lets a corrupted key produce invalid proofs")
+                .required(false),
+        )
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -97,7 +113,25 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
 
     let parameters = Parameters(backend_parameter, curve_parameter, scheme_parameter);
 
+    let seed = sub_matches
+        .value_of("seed")
+        .map(super::setup::parse_seed)
+        .transpose()?;
+
     match parameters {
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, _, SchemeParameter::G16) if seed.is_some() => {
+            let seed = seed.unwrap();
+            match prog {
+                ProgEnum::Bn128Program(p) => cli_generate_proof_seeded(p, seed, sub_matches),
+                ProgEnum::Bls12_381Program(p) => cli_generate_proof_seeded(p, seed, sub_matches),
+                ProgEnum::Bls12_377Program(p) => cli_generate_proof_seeded(p, seed, sub_matches),
+                ProgEnum::Bw6_761Program(p) => cli_generate_proof_seeded(p, seed, sub_matches),
+            }
+        }
+        _ if seed.is_some() => {
+            Err("A seed is only supported with the ark backend and the g16 scheme".to_string())
+        }
         #[cfg(feature = "bellman")]
         Parameters(BackendParameter::Bellman, _, SchemeParameter::G16) => match prog {
             ProgEnum::Bn128Program(p) => cli_generate_proof::<_, _, G16, Bellman>(p, sub_matches),
@@ -108,10 +142,10 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         },
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, _, SchemeParameter::G16) => match prog {
-            ProgEnum::Bn128Program(p) => cli_generate_proof::<_, _, G16, Ark>(p, sub_matches),
-            ProgEnum::Bls12_381Program(p) => cli_generate_proof::<_, _, G16, Ark>(p, sub_matches),
-            ProgEnum::Bls12_377Program(p) => cli_generate_proof::<_, _, G16, Ark>(p, sub_matches),
-            ProgEnum::Bw6_761Program(p) => cli_generate_proof::<_, _, G16, Ark>(p, sub_matches),
+            ProgEnum::Bn128Program(p) => cli_generate_proof_ark_g16(p, sub_matches),
+            ProgEnum::Bls12_381Program(p) => cli_generate_proof_ark_g16(p, sub_matches),
+            ProgEnum::Bls12_377Program(p) => cli_generate_proof_ark_g16(p, sub_matches),
+            ProgEnum::Bw6_761Program(p) => cli_generate_proof_ark_g16(p, sub_matches),
         },
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, _, SchemeParameter::GM17) => match prog {
@@ -146,6 +180,66 @@ fn cli_generate_proof<
 ) -> Result<(), String> {
     println!("Generating proof...");
 
+    let (witness, pk) = read_witness_and_proving_key(&program, sub_matches)?;
+
+    let proof = B::generate_proof(program, witness, pk);
+
+    write_proof(proof, sub_matches)
+}
+
+#[cfg(feature = "ark")]
+fn cli_generate_proof_ark_g16<
+    T: Field + ArkFieldExtensions,
+    I: Iterator<Item = ir::Statement<T>>,
+>(
+    program: ir::ProgIterator<T, I>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Generating proof...");
+
+    let (witness, pk) = read_witness_and_proving_key(&program, sub_matches)?;
+    check_proving_key::<T>(&pk, sub_matches)?;
+
+    let proof = <Ark as Backend<T, G16>>::generate_proof(program, witness, pk);
+
+    write_proof(proof, sub_matches)
+}
+
+/// Rejects a proving key with invalid points, unless `--unchecked-proving-key` is set
+#[cfg(feature = "ark")]
+fn check_proving_key<T: Field + ArkFieldExtensions>(
+    pk: &[u8],
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    if sub_matches.is_present("unchecked-proving-key") {
+        return Ok(());
+    }
+    Ark::check_proving_key::<T>(pk)
+}
+
+#[cfg(feature = "ark")]
+fn cli_generate_proof_seeded<
+    T: Field + ArkFieldExtensions,
+    I: Iterator<Item = ir::Statement<T>>,
+>(
+    program: ir::ProgIterator<T, I>,
+    seed: [u8; 32],
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Generating proof...");
+
+    let (witness, pk) = read_witness_and_proving_key(&program, sub_matches)?;
+    check_proving_key::<T>(&pk, sub_matches)?;
+
+    let proof = Ark::generate_proof_with_seed(program, witness, pk, seed);
+
+    write_proof(proof, sub_matches)
+}
+
+fn read_witness_and_proving_key<T: Field, I: Iterator<Item = ir::Statement<T>>>(
+    program: &ir::ProgIterator<T, I>,
+    sub_matches: &ArgMatches,
+) -> Result<(ir::Witness<T>, Vec<u8>), String> {
     // deserialize witness
     let witness_path = Path::new(sub_matches.value_of("witness").unwrap());
     let witness_file = File::open(&witness_path)
@@ -154,8 +248,9 @@ fn cli_generate_proof<
     let witness = ir::Witness::read(witness_file)
         .map_err(|why| format!("Could not load witness: {:?}", why))?;
 
+    witness.check_signature(&program.arguments, program.return_count)?;
+
     let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
-    let proof_path = Path::new(sub_matches.value_of("proof-path").unwrap());
 
     let pk_file = File::open(&pk_path)
         .map_err(|why| format!("Could not open {}: {}", pk_path.display(), why))?;
@@ -166,11 +261,25 @@ fn cli_generate_proof<
         .read_to_end(&mut pk)
         .map_err(|why| format!("Could not read {}: {}", pk_path.display(), why))?;
 
-    let proof = B::generate_proof(program, witness, pk);
+    Ok((witness, pk))
+}
+
+fn write_proof<T: Field, S: Scheme<T>>(
+    proof: Proof<T, S>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    let proof_path = Path::new(sub_matches.value_of("proof-path").unwrap());
     let mut proof_file = File::create(proof_path).unwrap();
 
+    // write the inputs as padded 0x-prefixed hex whatever the backend, the form `verify` expects
+    let inputs = proof
+        .inputs
+        .iter()
+        .map(|i| parse_hex_input::<T>(i).map(|i| format_hex_input(&i)))
+        .collect::<Result<Vec<_>, _>>()?;
+
     let proof =
-        serde_json::to_string_pretty(&TaggedProof::<T, S>::new(proof.proof, proof.inputs)).unwrap();
+        serde_json::to_string_pretty(&TaggedProof::<T, S>::new(proof.proof, inputs)).unwrap();
     proof_file
         .write(proof.as_bytes())
         .map_err(|why| format!("Could not write to {}: {}", proof_path.display(), why))?;
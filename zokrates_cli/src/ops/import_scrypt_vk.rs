@@ -0,0 +1,67 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::Write;
+use std::path::Path;
+use zokrates_field::Bn128Field;
+use zokrates_proof_systems::groth16::import_scrypt_verification_key;
+use zokrates_proof_systems::{G2Order, TaggedVerificationKey, G16};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("import-scrypt-vk")
+        .about("Recovers a verification key from the constants of an exported sCrypt verifier")
+        .arg(
+            Arg::with_name("input")
+                .short("i")
+                .long("input")
+                .help("Path of the exported verifier")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value("verifier/src/contracts/snark.ts"),
+        )
+        .arg(
+            Arg::with_name("output")
+                .short("o")
+                .long("output")
+                .help("Path of the recovered verification key")
+                .value_name("FILE")
+                .takes_value(true)
+                .required(false)
+                .default_value("imported_verification.key"),
+        )
+        .arg(
+            Arg::with_name("g2-order")
+                .long("g2-order")
+                .help("Order of the Fq2 components of G2 points the verifier was exported with")
+                .value_name("ORDER")
+                .takes_value(true)
+                .required(false)
+                .possible_values(&["standard", "scrypt"])
+                .default_value("scrypt"),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let order = G2Order::try_from(sub_matches.value_of("g2-order").unwrap())?;
+
+    let input_path = Path::new(sub_matches.value_of("input").unwrap());
+    let text = std::fs::read_to_string(input_path)
+        .map_err(|why| format!("Could not open {}: {}", input_path.display(), why))?;
+
+    let vk = import_scrypt_verification_key(&text, order)?;
+
+    let output_path = Path::new(sub_matches.value_of("output").unwrap());
+    let mut output_file = File::create(output_path)
+        .map_err(|why| format!("Could not create {}: {}", output_path.display(), why))?;
+    output_file
+        .write_all(
+            serde_json::to_string_pretty(&TaggedVerificationKey::<Bn128Field, G16>::new(vk))
+                .unwrap()
+                .as_bytes(),
+        )
+        .map_err(|why| format!("Could not write to {}: {}", output_path.display(), why))?;
+
+    println!("Verification key written to '{}'", output_path.display());
+    Ok(())
+}
@@ -1,14 +1,20 @@
+#[cfg(any(feature = "bellman", feature = "ark"))]
+pub mod bench_export;
 pub mod check;
 pub mod compile;
 pub mod compute_witness;
 pub mod export_verifier;
 pub mod export_verifier_scrypt; 
+#[cfg(feature = "ark")]
+pub mod gen_test_vectors;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 pub mod generate_proof;
 pub mod generate_smtlib2;
+pub mod import_scrypt_vk;
 pub mod inspect;
 #[cfg(feature = "bellman")]
 pub mod mpc;
+pub mod pairing_lib_info;
 pub mod print_proof;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 pub mod setup;
@@ -0,0 +1,71 @@
+use clap::{App, Arg, ArgMatches, SubCommand};
+use sha2::{Digest, Sha256};
+use std::convert::TryFrom;
+use zokrates_common::constants;
+use zokrates_common::helpers::CurveParameter;
+use zokrates_proof_systems::{
+    miller_loop_steps, scrypt_pairing_lib_bls12_381, scrypt_pairing_lib_bn128, BLS12_381_X,
+};
+
+pub fn subcommand() -> App<'static, 'static> {
+    SubCommand::with_name("pairing-lib-info")
+        .about("Prints the hash of the sCrypt pairing library embedded in exported verifiers")
+        .arg(
+            Arg::with_name("curve")
+                .short("c")
+                .long("curve")
+                .help("Curve of the pairing library")
+                .takes_value(true)
+                .required(false)
+                .possible_values(&[constants::BN128, constants::BLS12_381])
+                .default_value(constants::BN128),
+        )
+}
+
+pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
+    let curve = CurveParameter::try_from(sub_matches.value_of("curve").unwrap())?;
+    let lib = pairing_lib(&curve)?;
+
+    println!("curve: {}", curve);
+    println!("version: {}", env!("CARGO_PKG_VERSION"));
+    println!("sha256: {}", pairing_lib_hash(lib));
+    println!("size: {} bytes", lib.len());
+    if curve == CurveParameter::Bls12_381 {
+        println!(
+            "miller loop steps: {}",
+            miller_loop_steps(BLS12_381_X).len()
+        );
+    }
+    Ok(())
+}
+
+fn pairing_lib(curve: &CurveParameter) -> Result<&'static str, String> {
+    match curve {
+        CurveParameter::Bn128 => Ok(scrypt_pairing_lib_bn128()),
+        CurveParameter::Bls12_381 => Ok(scrypt_pairing_lib_bls12_381()),
+        c => Err(format!("No sCrypt pairing library for curve {}", c)),
+    }
+}
+
+fn pairing_lib_hash(lib: &str) -> String {
+    hex::encode(Sha256::digest(lib.as_bytes()).as_slice())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hashes of the embedded libraries, to be updated along with any change to them
+    const BN128_HASH: &str = "b14d3b7e54c55a403f0eeebc2ec3a5ba4679696ab0ea9340c8221d6863cdd3d2";
+    const BLS12_381_HASH: &str = "f522bbf2beed027e702db1d00c5d1353b3d10625e6d2977ea63f8fffc1831f86";
+
+    #[test]
+    fn hash_is_stable() {
+        for (curve, expected) in [
+            (CurveParameter::Bn128, BN128_HASH),
+            (CurveParameter::Bls12_381, BLS12_381_HASH),
+        ] {
+            assert_eq!(pairing_lib_hash(pairing_lib(&curve).unwrap()), expected);
+        }
+    }
+}
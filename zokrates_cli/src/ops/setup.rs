@@ -11,6 +11,8 @@ use zokrates_ast::ir::{self, ProgEnum};
 use zokrates_bellman::Bellman;
 use zokrates_common::constants;
 use zokrates_common::helpers::*;
+#[cfg(feature = "ark")]
+use zokrates_field::ArkFieldExtensions;
 use zokrates_field::Field;
 #[cfg(any(feature = "bellman", feature = "ark"))]
 use zokrates_proof_systems::*;
@@ -78,6 +80,29 @@ pub fn subcommand() -> App<'static, 'static> {
                 .required(false)
                 .default_value(cli_constants::UNIVERSAL_SETUP_DEFAULT_PATH),
         )
+        .arg(
+            Arg::with_name("seed")
+                .long("seed")
+                .help("Hex encoded 32 byte seed of the setup randomness, for reproducible keys (ark backend and g16 scheme only)")
+                .value_name("SEED")
+                .takes_value(true)
+                .required(false),
+        )
+}
+
+pub(crate) fn parse_seed(seed: &str) -> Result<[u8; 32], String> {
+    let bytes = hex::decode(seed.trim_start_matches("0x"))
+        .map_err(|why| format!("Invalid seed `{}`: {}", seed, why))?;
+    if bytes.len() != 32 {
+        return Err(format!(
+            "Invalid seed `{}`: expected 32 bytes, found {}",
+            seed,
+            bytes.len()
+        ));
+    }
+    let mut res = [0u8; 32];
+    res.copy_from_slice(&bytes);
+    Ok(res)
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -95,7 +120,22 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
         sub_matches.value_of("proving-scheme").unwrap(),
     ))?;
 
+    let seed = sub_matches.value_of("seed").map(parse_seed).transpose()?;
+
     match parameters {
+        #[cfg(feature = "ark")]
+        Parameters(BackendParameter::Ark, _, SchemeParameter::G16) if seed.is_some() => {
+            let seed = seed.unwrap();
+            match prog {
+                ProgEnum::Bn128Program(p) => cli_setup_seeded(p, seed, sub_matches),
+                ProgEnum::Bls12_381Program(p) => cli_setup_seeded(p, seed, sub_matches),
+                ProgEnum::Bls12_377Program(p) => cli_setup_seeded(p, seed, sub_matches),
+                ProgEnum::Bw6_761Program(p) => cli_setup_seeded(p, seed, sub_matches),
+            }
+        }
+        _ if seed.is_some() => {
+            Err("A seed is only supported with the ark backend and the g16 scheme".to_string())
+        }
         #[cfg(feature = "bellman")]
         Parameters(BackendParameter::Bellman, _, SchemeParameter::G16) => match prog {
             ProgEnum::Bn128Program(p) => {
@@ -177,13 +217,34 @@ fn cli_setup_non_universal<
 ) -> Result<(), String> {
     println!("Performing setup...");
 
+    // run setup phase
+    let keypair = B::setup(program);
+
+    write_keypair(keypair, sub_matches)
+}
+
+#[cfg(feature = "ark")]
+fn cli_setup_seeded<T: Field + ArkFieldExtensions, I: Iterator<Item = ir::Statement<T>>>(
+    program: ir::ProgIterator<T, I>,
+    seed: [u8; 32],
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
+    println!("Performing setup...");
+
+    // run setup phase
+    let keypair = Ark::setup_with_seed(program, seed);
+
+    write_keypair(keypair, sub_matches)
+}
+
+fn write_keypair<T: Field, S: Scheme<T>>(
+    keypair: SetupKeypair<T, S>,
+    sub_matches: &ArgMatches,
+) -> Result<(), String> {
     // get paths for proving and verification keys
     let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
     let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
 
-    // run setup phase
-    let keypair = B::setup(program);
-
     // write verification key
     let mut vk_file = File::create(vk_path)
         .map_err(|why| format!("Could not create {}: {}", vk_path.display(), why))?;
@@ -222,35 +283,8 @@ fn cli_setup_universal<
 ) -> Result<(), String> {
     println!("Performing setup...");
 
-    // get paths for proving and verification keys
-    let pk_path = Path::new(sub_matches.value_of("proving-key-path").unwrap());
-    let vk_path = Path::new(sub_matches.value_of("verification-key-path").unwrap());
-
     // run setup phase
     let keypair = B::setup(srs, program)?;
 
-    // write verification key
-    let mut vk_file = File::create(vk_path)
-        .map_err(|why| format!("Could not create {}: {}", vk_path.display(), why))?;
-    vk_file
-        .write_all(
-            serde_json::to_string_pretty(&TaggedVerificationKey::<T, S>::new(keypair.vk))
-                .unwrap()
-                .as_bytes(),
-        )
-        .map_err(|why| format!("Could not write to {}: {}", vk_path.display(), why))?;
-
-    println!("Verification key written to '{}'", vk_path.display());
-
-    // write proving key
-    let mut pk_file = File::create(pk_path)
-        .map_err(|why| format!("Could not create {}: {}", pk_path.display(), why))?;
-    pk_file
-        .write_all(keypair.pk.as_ref())
-        .map_err(|why| format!("Could not write to {}: {}", pk_path.display(), why))?;
-
-    println!("Proving key written to '{}'", pk_path.display());
-    println!("Setup completed");
-
-    Ok(())
+    write_keypair(keypair, sub_matches)
 }
@@ -47,6 +47,12 @@ pub fn subcommand() -> App<'static, 'static> {
                 .possible_values(cli_constants::BACKENDS)
                 .default_value(constants::BELLMAN),
         )
+        .arg(
+            Arg::with_name("json")
+                .long("json")
+                .help("Print the verification result as JSON")
+                .required(false),
+        )
 }
 
 pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
@@ -114,59 +120,59 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
     match parameters {
         #[cfg(feature = "bellman")]
         Parameters(BackendParameter::Bellman, CurveParameter::Bn128, SchemeParameter::G16) => {
-            cli_verify::<Bn128Field, G16, Bellman>(vk, proof)
+            cli_verify::<Bn128Field, G16, Bellman>(vk, proof, sub_matches)
         }
         #[cfg(feature = "bellman")]
         Parameters(BackendParameter::Bellman, CurveParameter::Bls12_381, SchemeParameter::G16) => {
-            cli_verify::<Bls12_381Field, G16, Bellman>(vk, proof)
+            cli_verify::<Bls12_381Field, G16, Bellman>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bn128, SchemeParameter::G16) => {
-            cli_verify::<Bn128Field, G16, Ark>(vk, proof)
+            cli_verify::<Bn128Field, G16, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_381, SchemeParameter::G16) => {
-            cli_verify::<Bls12_381Field, G16, Ark>(vk, proof)
+            cli_verify::<Bls12_381Field, G16, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_377, SchemeParameter::G16) => {
-            cli_verify::<Bls12_377Field, G16, Ark>(vk, proof)
+            cli_verify::<Bls12_377Field, G16, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bw6_761, SchemeParameter::G16) => {
-            cli_verify::<Bw6_761Field, G16, Ark>(vk, proof)
+            cli_verify::<Bw6_761Field, G16, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bn128, SchemeParameter::GM17) => {
-            cli_verify::<Bn128Field, GM17, Ark>(vk, proof)
+            cli_verify::<Bn128Field, GM17, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_381, SchemeParameter::GM17) => {
-            cli_verify::<Bls12_381Field, GM17, Ark>(vk, proof)
+            cli_verify::<Bls12_381Field, GM17, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_377, SchemeParameter::GM17) => {
-            cli_verify::<Bls12_377Field, GM17, Ark>(vk, proof)
+            cli_verify::<Bls12_377Field, GM17, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bw6_761, SchemeParameter::GM17) => {
-            cli_verify::<Bw6_761Field, GM17, Ark>(vk, proof)
+            cli_verify::<Bw6_761Field, GM17, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bn128, SchemeParameter::MARLIN) => {
-            cli_verify::<Bn128Field, Marlin, Ark>(vk, proof)
+            cli_verify::<Bn128Field, Marlin, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_381, SchemeParameter::MARLIN) => {
-            cli_verify::<Bls12_381Field, Marlin, Ark>(vk, proof)
+            cli_verify::<Bls12_381Field, Marlin, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bls12_377, SchemeParameter::MARLIN) => {
-            cli_verify::<Bls12_377Field, Marlin, Ark>(vk, proof)
+            cli_verify::<Bls12_377Field, Marlin, Ark>(vk, proof, sub_matches)
         }
         #[cfg(feature = "ark")]
         Parameters(BackendParameter::Ark, CurveParameter::Bw6_761, SchemeParameter::MARLIN) => {
-            cli_verify::<Bw6_761Field, Marlin, Ark>(vk, proof)
+            cli_verify::<Bw6_761Field, Marlin, Ark>(vk, proof, sub_matches)
         }
         _ => unreachable!(),
     }
@@ -175,12 +181,27 @@ pub fn exec(sub_matches: &ArgMatches) -> Result<(), String> {
 fn cli_verify<T: Field, S: Scheme<T>, B: Backend<T, S>>(
     vk: serde_json::Value,
     proof: serde_json::Value,
+    sub_matches: &ArgMatches,
 ) -> Result<(), String> {
+    let curve = proof["curve"].as_str().unwrap().to_string();
+    let scheme = proof["scheme"].as_str().unwrap().to_string();
+
     // convert the JSON vk and proof to the correct types
     let vk = serde_json::from_value(vk)
         .map_err(|why| format!("Could not deserialize verification key: {}", why))?;
     let proof: Proof<T, S> = serde_json::from_value(proof)
         .map_err(|why| format!("Could not deserialize proof: {}", why))?;
+    proof.check_inputs()?;
+
+    if sub_matches.is_present("json") {
+        let inputs = proof.inputs.clone();
+        let verified = B::verify(vk, proof);
+        println!(
+            "{}",
+            verification_json::<T>(verified, &inputs, &scheme, &curve)?
+        );
+        return Ok(());
+    }
 
     println!("Performing verification...");
     println!(
@@ -193,3 +214,53 @@ fn cli_verify<T: Field, S: Scheme<T>, B: Backend<T, S>>(
 
     Ok(())
 }
+
+/// Builds the JSON result of a verification, with the public inputs in decimal
+fn verification_json<T: Field>(
+    verified: bool,
+    inputs: &[String],
+    scheme: &str,
+    curve: &str,
+) -> Result<serde_json::Value, String> {
+    let inputs = inputs
+        .iter()
+        .map(|i| parse_hex_input::<T>(i).map(|i| i.to_dec_string()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(serde_json::json!({
+        "verified": verified,
+        "inputs": inputs,
+        "scheme": scheme,
+        "curve": curve,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verification_json_decimal_inputs() {
+        let inputs = vec![
+            "0x0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+            "0x00000000000000000000000000000000000000000000000000000000000000ff".to_string(),
+        ];
+
+        assert_eq!(
+            verification_json::<Bn128Field>(true, &inputs, "g16", "bn128").unwrap(),
+            serde_json::json!({
+                "verified": true,
+                "inputs": ["1", "255"],
+                "scheme": "g16",
+                "curve": "bn128",
+            })
+        );
+    }
+
+    #[test]
+    fn verification_json_invalid_input() {
+        let inputs = vec!["0xzz".to_string()];
+
+        assert!(verification_json::<Bn128Field>(false, &inputs, "g16", "bn128").is_err());
+    }
+}
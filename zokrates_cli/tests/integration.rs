@@ -284,6 +284,13 @@ mod integration {
                         .succeeds()
                         .unwrap();
 
+                    // the public inputs are written in the form `verify` reads them
+                    let proof: serde_json::Value =
+                        from_reader(File::open(&proof_path).unwrap()).unwrap();
+                    for input in proof["inputs"].as_array().unwrap() {
+                        assert!(input.as_str().unwrap().starts_with("0x"));
+                    }
+
                     // CLI VERIFICATION
                     assert_cli::Assert::main_binary()
                         .with_args(&[
@@ -22,6 +22,7 @@ use std::convert::TryFrom;
 use zokrates_ast::common::embed::*;
 use zokrates_ast::common::FlatEmbed;
 use zokrates_ast::common::{RuntimeError, Variable};
+use zokrates_ast::flat::div_lowering::DivLowerer;
 use zokrates_ast::flat::*;
 use zokrates_ast::ir::Solver;
 use zokrates_ast::zir::types::{Type, UBitwidth};
@@ -77,8 +78,17 @@ impl<'ast, T: Field> Iterator for FlattenerIteratorInner<'ast, T> {
         while self.statements_flattened.is_empty() {
             match self.statements.pop_front() {
                 Some(s) => {
-                    self.flattener
-                        .flatten_statement(&mut self.statements_flattened, s);
+                    let mut flattened = FlatStatements::new();
+                    self.flattener.flatten_statement(&mut flattened, s);
+                    // the conversion to IR only takes products, so divisions are lowered here,
+                    // where the flattener can still issue variables
+                    for s in flattened {
+                        self.statements_flattened
+                            .extend(DivLowerer::lower_statement(
+                                &mut self.flattener.next_var_idx,
+                                s,
+                            ));
+                    }
                 }
                 None => {
                     break;
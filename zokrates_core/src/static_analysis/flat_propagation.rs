@@ -59,6 +59,9 @@ impl<T: Field> Folder<T> for Propagator<T> {
                     (e1, e2) => FlatExpression::Mult(box e1, box e2),
                 }
             }
+            FlatExpression::Div(box e1, box e2) => {
+                FlatExpression::Div(box self.fold_expression(e1), box self.fold_expression(e2))
+            }
         }
     }
 }
@@ -412,6 +412,51 @@ mod tests {
         assert_eq!(res[247], Bn128Field::from(0));
     }
 
+    #[test]
+    fn lowered_div() {
+        use zokrates_ast::flat::div_lowering::DivLowerer;
+        use zokrates_ast::flat::{FlatExpression, FlatProg, FlatProgIterator, FlatStatement};
+        use zokrates_ast::ir::{from_flat::from_flat, Parameter};
+
+        let a = Variable::new(0);
+        let b = Variable::new(1);
+
+        // return a / b
+        let p: FlatProg<Bn128Field> = DivLowerer::lower(FlatProg {
+            arguments: vec![Parameter::private(a), Parameter::private(b)],
+            statements: vec![FlatStatement::Condition(
+                FlatExpression::Identifier(Variable::public(0)),
+                FlatExpression::Div(
+                    Box::new(FlatExpression::Identifier(a)),
+                    Box::new(FlatExpression::Identifier(b)),
+                ),
+                RuntimeError::Equal,
+            )],
+            return_count: 1,
+        });
+        let program = || {
+            from_flat(FlatProgIterator {
+                arguments: p.arguments.clone(),
+                statements: p.statements.clone(),
+                return_count: p.return_count,
+            })
+        };
+
+        let witness = Interpreter::default()
+            .execute(program(), &[Bn128Field::from(6), Bn128Field::from(3)])
+            .unwrap();
+        assert_eq!(witness.return_values(), vec![Bn128Field::from(2)]);
+
+        assert_eq!(
+            Interpreter::default()
+                .execute(program(), &[Bn128Field::from(6), Bn128Field::from(0)])
+                .unwrap_err(),
+            Error::UnsatisfiedConstraint {
+                error: Some(RuntimeError::Inverse)
+            }
+        );
+    }
+
     #[test]
     fn five_hundred_bits_of_1() {
         let inputs = vec![Bn128Field::from(1)];
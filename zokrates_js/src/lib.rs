@@ -29,8 +29,8 @@ use zokrates_field::{Bls12_377Field, Bls12_381Field, Bn128Field, Bw6_761Field, F
 use zokrates_proof_systems::groth16::G16;
 use zokrates_proof_systems::{
     Backend, Marlin, NonUniversalBackend, NonUniversalScheme, Proof, Scheme,
-    SolidityCompatibleField, SolidityCompatibleScheme,
-    ScryptCompatibleField, ScryptCompatibleScheme,
+    SolidityCompatibleField, SolidityCompatibleScheme, SolidityExportConfig,
+    ScryptCompatibleField, ScryptCompatibleScheme, ScryptExportConfig,
     TaggedKeypair, TaggedProof,
     UniversalBackend, UniversalScheme, GM17,
 };
@@ -378,6 +378,10 @@ mod internal {
         let ir_witness: ir::Witness<T> = ir::Witness::read(str_witness.as_bytes())
             .map_err(|err| JsValue::from_str(&format!("Could not read witness: {}", err)))?;
 
+        ir_witness
+            .check_signature(&prog.arguments, prog.return_count)
+            .map_err(|err| JsValue::from_str(&err))?;
+
         let proof = B::generate_proof(prog, ir_witness, pk.to_vec());
         Ok(JsValue::from_serde(&TaggedProof::<T, S>::new(proof.proof, proof.inputs)).unwrap())
     }
@@ -428,7 +432,9 @@ mod internal {
         let vk: S::VerificationKey =
             serde_json::from_value(vk).map_err(|err| JsValue::from_str(&format!("{}", err)))?;
 
-        Ok(JsValue::from_str(&S::export_solidity_verifier(vk)))
+        S::export_solidity_verifier(vk, &SolidityExportConfig::default())
+            .map(|verifier| JsValue::from_str(&verifier))
+            .map_err(|err| JsValue::from_str(&err))
     }
 
     pub fn export_scrypt_verifier<T: ScryptCompatibleField, S: ScryptCompatibleScheme<T>, B: Backend<T, S>>(
@@ -446,7 +452,13 @@ mod internal {
             .ok_or_else(|| "`curve` should be a string".to_string())?;
         let curve_parameter = CurveParameter::try_from(vk_curve)?;
 
-        Ok(JsValue::from_str(&S::export_scrypt_verifier(_vk1, curve_parameter)))
+        let verifier = S::export_scrypt_verifier(
+            _vk1,
+            curve_parameter,
+            &ScryptExportConfig::default(),
+        )?;
+
+        Ok(JsValue::from_str(&verifier))
     }
 
     pub fn get_miller_beta_alpha_string<T: Field, S: Scheme<T>, B: Backend<T, S>>(
@@ -18,6 +18,7 @@ use zokrates_ast::ir;
 use serde::{Deserialize, Serialize};
 
 use rand_0_4::Rng;
+use std::convert::TryFrom;
 use std::io::{Read, Write};
 
 use zokrates_field::Field;
@@ -44,15 +45,73 @@ impl<T: Field, S: Scheme<T>> Proof<T, S> {
     pub fn new(proof: S::ProofPoints, inputs: Vec<String>) -> Self {
         Proof { proof, inputs }
     }
+
+    /// Checks that every public input is `0x`-prefixed hex of an element of `T`, the form
+    /// all verifiers expect
+    pub fn check_inputs(&self) -> Result<(), String> {
+        self.inputs
+            .iter()
+            .try_for_each(|i| parse_hex_input::<T>(i).map(|_| ()))
+    }
+}
+
+/// Formats a public input as `0x`-prefixed big-endian hex, padded to the size of `T`
+pub fn format_hex_input<T: Field>(input: &T) -> String {
+    let mut bytes = input.to_byte_vector();
+    bytes.reverse();
+    format!("0x{}", hex::encode(&bytes))
+}
+
+/// Parses a public input serialized as `0x`-prefixed hex
+pub fn parse_hex_input<T: Field>(input: &str) -> Result<T, String> {
+    input
+        .strip_prefix("0x")
+        .and_then(|i| T::try_from_str(i, 16).ok())
+        .ok_or_else(|| {
+            format!(
+                "Public input `{}` is not a 0x-prefixed hex element of the {} field",
+                input,
+                T::name()
+            )
+        })
 }
 
 pub type Fr = String;
 pub type Fq = String;
 pub type Fq2 = (String, String);
 
+/// Encodings of a point accepted on deserialization: `[x, y]`, as serialized by ZoKrates,
+/// or `{ "x": x, "y": y }`, as serialized by some other tools
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum PointEncoding<C> {
+    Array(C, C),
+    Object { x: C, y: C },
+}
+
+impl<C> PointEncoding<C> {
+    fn into_coordinates(self) -> (C, C) {
+        match self {
+            PointEncoding::Array(x, y) | PointEncoding::Object { x, y } => (x, y),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
+#[serde(from = "PointEncoding<Fq>")]
 pub struct G1Affine(pub Fq, pub Fq);
 
+impl From<PointEncoding<Fq>> for G1Affine {
+    fn from(e: PointEncoding<Fq>) -> Self {
+        let (x, y) = e.into_coordinates();
+        G1Affine(x, y)
+    }
+}
+
+/// Compressed G1 point: the x coordinate and whether the y coordinate is odd
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CompressedG1Affine(pub Fq, pub bool);
+
 #[derive(Serialize, Deserialize, Clone)]
 #[serde(untagged)]
 pub enum G2Affine {
@@ -71,12 +130,28 @@ impl ToString for G2Affine {
 
 // When G2 is defined on Fq2 field
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "PointEncoding<Fq2>")]
 pub struct G2AffineFq2(pub Fq2, pub Fq2);
 
+impl From<PointEncoding<Fq2>> for G2AffineFq2 {
+    fn from(e: PointEncoding<Fq2>) -> Self {
+        let (x, y) = e.into_coordinates();
+        G2AffineFq2(x, y)
+    }
+}
+
 // When G2 is defined on a Fq field (BW6_761 curve)
 #[derive(Serialize, Deserialize, Clone)]
+#[serde(from = "PointEncoding<Fq>")]
 pub struct G2AffineFq(pub Fq, pub Fq);
 
+impl From<PointEncoding<Fq>> for G2AffineFq {
+    fn from(e: PointEncoding<Fq>) -> Self {
+        let (x, y) = e.into_coordinates();
+        G2AffineFq(x, y)
+    }
+}
+
 impl ToString for G1Affine {
     fn to_string(&self) -> String {
         format!("{}, {}", self.0, self.1)
@@ -102,47 +177,146 @@ impl ToString for G2AffineFq2 {
 
 /* =============== add by sCrypt */
 
-fn hex_to_decimal(hex_string: String) -> Option<String> {
-    let hex_string_stripped = hex_string.strip_prefix("0x").unwrap();
-    let bigint = BigUint::from_str_radix(hex_string_stripped, 16).ok()?;
-    Some(bigint.to_string())
+/// Decimal representation of a hex coordinate, with or without a `0x`/`0X` prefix
+fn hex_to_decimal(hex_string: &str) -> Result<String, String> {
+    fq_to_biguint(hex_string)
+        .map(|n| n.to_string())
+        .map_err(|_| format!("invalid coordinate: `{}`", hex_string))
+}
+
+/// Parses a hex field element, with or without a `0x`/`0X` prefix
+pub fn fq_to_biguint(e: &str) -> Result<BigUint, String> {
+    let stripped = e
+        .strip_prefix("0x")
+        .or_else(|| e.strip_prefix("0X"))
+        .unwrap_or(e);
+    BigUint::from_str_radix(stripped, 16).map_err(|_| format!("Invalid field element `{}`", e))
+}
+
+/// Parses both components of an `Fq2` element, see `fq_to_biguint`
+pub fn fq2_to_biguint_pair(e: &Fq2) -> Result<(BigUint, BigUint), String> {
+    Ok((fq_to_biguint(&e.0)?, fq_to_biguint(&e.1)?))
+}
+
+/// Canonical decimal representation of a hex field element, regardless of casing and `0x` prefix
+pub(crate) fn canonical_decimal(hex_string: &str) -> String {
+    fq_to_biguint(hex_string)
+        .map(|n| n.to_string())
+        .unwrap_or_else(|_| hex_string.to_string())
+}
+
+/// Whether the coordinates of a point encode the point at infinity, which the sCrypt
+/// `createCurvePoint` and `createTwistPoint` only recognize as all zero coordinates. Keys
+/// contain it in `gamma_abc` for public inputs that no constraint uses, and each backend
+/// writes it differently:
+/// - bellman writes zero coordinates on bn128, and on bls12_381 an otherwise zero `x` whose
+///   first serialized component carries the infinity flag, bit 6 of its top byte
+/// - ark writes zero coordinates but for the first component of `y`, which is one
+///
+/// No curve with an sCrypt verifier has a point with these coordinates.
+fn is_infinity(x: &[&Fq], y: &[&Fq]) -> bool {
+    let digits = |c: &str| {
+        c.strip_prefix("0x")
+            .or_else(|| c.strip_prefix("0X"))
+            .unwrap_or(c)
+            .to_string()
+    };
+    let is_zero = |c: &str| {
+        let c = digits(c);
+        !c.is_empty() && c.chars().all(|d| d == '0')
+    };
+    let is_flag = |c: &str| {
+        let c = digits(c);
+        c.len() > 2 && c.starts_with("40") && c[2..].chars().all(|d| d == '0')
+    };
+    let is_one = |c: &str| digits(c).trim_start_matches('0') == "1";
+
+    x.iter().all(|c| is_zero(c) || is_flag(c))
+        && y.iter().filter(|c| !is_zero(c)).count() <= 1
+        && y.iter().all(|c| is_zero(c) || is_one(c))
+}
+
+/// Decimal coordinates of a point, `x` components first, with the point at infinity in the
+/// all zero encoding of the sCrypt library
+fn scrypt_coordinates(x: &[&Fq], y: &[&Fq]) -> Result<Vec<String>, String> {
+    match is_infinity(x, y) {
+        true => Ok(vec!["0".to_string(); x.len() + y.len()]),
+        false => x.iter().chain(y).map(|c| hex_to_decimal(c)).collect(),
+    }
 }
 
 pub trait ToScryptString {
-    fn to_scrypt_string(&self) -> String;
+    fn to_scrypt_string(&self) -> Result<String, String>;
+
+    /// The same literal on a single line, for large arrays of points
+    fn to_scrypt_string_compact(&self) -> Result<String, String>;
 }
 
 
 impl ToScryptString for G1Affine {
-    fn to_scrypt_string(&self) -> String {
-        format!(
+    fn to_scrypt_string(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&self.0], &[&self.1])?;
+        Ok(format!(
             "{{
                 x: {}n,
                 y: {}n
             }}",
-            hex_to_decimal(self.0.clone()).unwrap(), 
-            hex_to_decimal(self.1.clone()).unwrap()
-        )
+            c[0], c[1]
+        ))
+    }
+
+    fn to_scrypt_string_compact(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&self.0], &[&self.1])?;
+        Ok(format!("{{ x: {}n, y: {}n }}", c[0], c[1]))
+    }
+}
+
+impl G1Affine {
+    /// Whether this is the point at infinity, in the encoding of any backend
+    pub(crate) fn is_infinity(&self) -> bool {
+        is_infinity(&[&self.0], &[&self.1])
+    }
+
+    /// sCrypt `PackedG1Point` literal: the x coordinate and the parity of y
+    pub fn to_packed_scrypt_string(&self) -> Result<String, String> {
+        if self.is_infinity() {
+            return Err("The point at infinity cannot be packed".to_string());
+        }
+        let y = fq_to_biguint(&self.1).map_err(|_| format!("invalid coordinate: `{}`", self.1))?;
+        Ok(format!(
+            "{{
+                x: {}n,
+                yOdd: {}
+            }}",
+            hex_to_decimal(&self.0)?,
+            y.bit(0)
+        ))
     }
 }
 
 impl ToScryptString for G2AffineFq {
-    fn to_scrypt_string(&self) -> String {
-        format!(
+    fn to_scrypt_string(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&self.0], &[&self.1])?;
+        Ok(format!(
             "{{
                 x: {}n,
                 y: {}n
             }}",
-            hex_to_decimal(self.0.clone()).unwrap(), 
-            hex_to_decimal(self.1.clone()).unwrap()
-        )
+            c[0], c[1]
+        ))
+    }
+
+    fn to_scrypt_string_compact(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&self.0], &[&self.1])?;
+        Ok(format!("{{ x: {}n, y: {}n }}", c[0], c[1]))
     }
 }
 
 
 impl ToScryptString for G2AffineFq2 {
-    fn to_scrypt_string(&self) -> String {
-        format!(
+    fn to_scrypt_string(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&(self.0).0, &(self.0).1], &[&(self.1).0, &(self.1).1])?;
+        Ok(format!(
             "{{
                 x: {{
                     x: {}n,
@@ -153,21 +327,121 @@ impl ToScryptString for G2AffineFq2 {
                     y: {}n
                 }}
             }}",
-            hex_to_decimal((self.0).0.clone()).unwrap(),
-            hex_to_decimal((self.0).1.clone()).unwrap(),
-            hex_to_decimal((self.1).0.clone()).unwrap(),
-            hex_to_decimal((self.1).1.clone()).unwrap()
-        )
+            c[0], c[1], c[2], c[3]
+        ))
+    }
+
+    fn to_scrypt_string_compact(&self) -> Result<String, String> {
+        let c = scrypt_coordinates(&[&(self.0).0, &(self.0).1], &[&(self.1).0, &(self.1).1])?;
+        Ok(format!(
+            "{{ x: {{ x: {}n, y: {}n }}, y: {{ x: {}n, y: {}n }} }}",
+            c[0], c[1], c[2], c[3]
+        ))
     }
 }
 
 impl ToScryptString for G2Affine {
-    fn to_scrypt_string(&self) -> String {
+    fn to_scrypt_string(&self) -> Result<String, String> {
         match self {
             G2Affine::Fq(e) => e.to_scrypt_string(),
             G2Affine::Fq2(e) => e.to_scrypt_string(),
         }
     }
+
+    fn to_scrypt_string_compact(&self) -> Result<String, String> {
+        match self {
+            G2Affine::Fq(e) => e.to_scrypt_string_compact(),
+            G2Affine::Fq2(e) => e.to_scrypt_string_compact(),
+        }
+    }
+}
+
+/// Order of the two components of each Fq2 coordinate in emitted G2 points
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum G2Order {
+    /// Imaginary component first, as in EIP-197 and the sCrypt `TwistPoint` type
+    Standard,
+    /// Components as stored in the verification key, swapped on-chain by `createTwistPoint`
+    Scrypt,
+}
+
+impl Default for G2Order {
+    fn default() -> Self {
+        G2Order::Scrypt
+    }
+}
+
+impl TryFrom<&str> for G2Order {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "standard" => Ok(G2Order::Standard),
+            "scrypt" => Ok(G2Order::Scrypt),
+            _ => Err(format!("Unknown G2 order {}", s)),
+        }
+    }
+}
+
+/// Component order of the Fq2 coordinates of the G2 point of the proofs a verifier accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProofEncoding {
+    /// Components as output by ZoKrates, swapped on-chain by `createTwistPoint`
+    Scrypt,
+    /// Imaginary component first, as in EIP-197, swapped back by the verifier
+    Eip197,
+}
+
+impl Default for ProofEncoding {
+    fn default() -> Self {
+        ProofEncoding::Scrypt
+    }
+}
+
+impl TryFrom<&str> for ProofEncoding {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        match s {
+            "scrypt" => Ok(ProofEncoding::Scrypt),
+            "eip197" => Ok(ProofEncoding::Eip197),
+            _ => Err(format!("Unknown proof encoding {}", s)),
+        }
+    }
+}
+
+impl G2AffineFq2 {
+    /// Swaps the two components of each Fq2 coordinate
+    pub fn swap_components(&self) -> Self {
+        G2AffineFq2(
+            ((self.0).1.clone(), (self.0).0.clone()),
+            ((self.1).1.clone(), (self.1).0.clone()),
+        )
+    }
+}
+
+impl G2Affine {
+    /// Whether this is the point at infinity, in the encoding of any backend
+    pub(crate) fn is_infinity(&self) -> bool {
+        match self {
+            G2Affine::Fq(e) => is_infinity(&[&e.0], &[&e.1]),
+            G2Affine::Fq2(e) => is_infinity(&[&(e.0).0, &(e.0).1], &[&(e.1).0, &(e.1).1]),
+        }
+    }
+
+    pub fn coordinates(&self) -> Vec<&Fq> {
+        match self {
+            G2Affine::Fq(e) => vec![&e.0, &e.1],
+            G2Affine::Fq2(e) => vec![&(e.0).0, &(e.0).1, &(e.1).0, &(e.1).1],
+        }
+    }
+
+    pub fn with_order(&self, order: G2Order) -> Self {
+        match (self, order) {
+            (G2Affine::Fq2(e), G2Order::Standard) => G2Affine::Fq2(e.swap_components()),
+            _ => self.clone(),
+        }
+    }
 }
 /* =============== end */
 
@@ -220,3 +494,144 @@ pub trait MpcBackend<T: Field, S: Scheme<T>> {
 
     fn export_keypair<R: Read>(params: &mut R) -> Result<SetupKeypair<T, S>, String>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use regex::Regex;
+
+    fn g2() -> G2Affine {
+        G2Affine::Fq2(G2AffineFq2(
+            ("0x1".to_string(), "0x2".to_string()),
+            ("0x3".to_string(), "0x4".to_string()),
+        ))
+    }
+
+    fn scrypt_values(s: &str) -> Vec<String> {
+        Regex::new(r"(\d+)n")
+            .unwrap()
+            .captures_iter(s)
+            .map(|c| c.get(1).unwrap().as_str().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn hex_input_round_trip() {
+        use zokrates_field::{Bn128Field, Bw6_761Field};
+
+        let input = Bn128Field::from(255);
+        let hex = format_hex_input(&input);
+        assert_eq!(hex, format!("0x{:0>64}", "ff"));
+        assert_eq!(parse_hex_input::<Bn128Field>(&hex).unwrap(), input);
+
+        let input = Bw6_761Field::max_value();
+        let hex = format_hex_input(&input);
+        assert_eq!(parse_hex_input::<Bw6_761Field>(&hex).unwrap(), input);
+
+        assert!(parse_hex_input::<Bn128Field>("255").is_err());
+        assert!(parse_hex_input::<Bn128Field>("0xzz").is_err());
+    }
+
+    #[test]
+    fn infinity_scrypt_string() {
+        let zero = format!("0x{:0>64}", "");
+        let one = format!("0x{:0>64}", "1");
+
+        // as written by ark
+        let g1 = G1Affine(zero.clone(), one.clone());
+        assert_eq!(g1.to_scrypt_string_compact().unwrap(), "{ x: 0n, y: 0n }");
+        assert_eq!(
+            scrypt_values(&g1.to_scrypt_string().unwrap()),
+            vec!["0", "0"]
+        );
+
+        // as written by bellman on bls12_381
+        let flagged = format!("0x40{:0>94}", "");
+        let g1 = G1Affine(flagged.clone(), format!("0x{:0>96}", ""));
+        assert_eq!(g1.to_scrypt_string_compact().unwrap(), "{ x: 0n, y: 0n }");
+
+        let g2 = G2Affine::Fq2(G2AffineFq2(
+            (zero.clone(), zero.clone()),
+            (one.clone(), zero.clone()),
+        ));
+        assert_eq!(
+            g2.with_order(G2Order::Standard)
+                .to_scrypt_string_compact()
+                .unwrap(),
+            "{ x: { x: 0n, y: 0n }, y: { x: 0n, y: 0n } }"
+        );
+
+        // finite points are left as they are
+        let g1 = G1Affine(one.clone(), one);
+        assert_eq!(g1.to_scrypt_string_compact().unwrap(), "{ x: 1n, y: 1n }");
+    }
+
+    #[test]
+    fn g2_scrypt_order() {
+        let s = g2().with_order(G2Order::Scrypt).to_scrypt_string().unwrap();
+        assert_eq!(scrypt_values(&s), vec!["1", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn compact_scrypt_string() {
+        let g1 = G1Affine("0x1".to_string(), "0x2".to_string());
+        assert_eq!(g1.to_scrypt_string_compact().unwrap(), "{ x: 1n, y: 2n }");
+        assert_eq!(
+            scrypt_values(&g1.to_scrypt_string().unwrap()),
+            scrypt_values(&g1.to_scrypt_string_compact().unwrap())
+        );
+
+        assert_eq!(
+            g2().to_scrypt_string_compact().unwrap(),
+            "{ x: { x: 1n, y: 2n }, y: { x: 3n, y: 4n } }"
+        );
+        assert_eq!(
+            G2Affine::Fq(G2AffineFq("0x3".to_string(), "0x4".to_string()))
+                .to_scrypt_string_compact()
+                .unwrap(),
+            "{ x: 3n, y: 4n }"
+        );
+    }
+
+    #[test]
+    fn g2_standard_order() {
+        let s = g2().with_order(G2Order::Standard).to_scrypt_string().unwrap();
+        assert_eq!(scrypt_values(&s), vec!["2", "1", "4", "3"]);
+    }
+
+    #[test]
+    fn parse_field_elements() {
+        assert_eq!(fq_to_biguint("0x1f").unwrap(), BigUint::from(31u32));
+        assert_eq!(fq_to_biguint("0X1F").unwrap(), BigUint::from(31u32));
+        assert_eq!(fq_to_biguint("1f").unwrap(), BigUint::from(31u32));
+        assert_eq!(
+            fq2_to_biguint_pair(&("0x1".to_string(), "0x2".to_string())).unwrap(),
+            (BigUint::from(1u32), BigUint::from(2u32))
+        );
+    }
+
+    #[test]
+    fn reject_malformed_field_elements() {
+        assert!(fq_to_biguint("").is_err());
+        assert!(fq_to_biguint("0x").is_err());
+        assert!(fq_to_biguint("0xzz").is_err());
+        assert_eq!(
+            fq2_to_biguint_pair(&("0x1".to_string(), "bug".to_string())).unwrap_err(),
+            "Invalid field element `bug`"
+        );
+    }
+
+    #[test]
+    fn hex_coordinates_to_decimal() {
+        assert_eq!(hex_to_decimal("0x01").unwrap(), "1");
+        assert_eq!(hex_to_decimal("01").unwrap(), "1");
+        assert_eq!(
+            hex_to_decimal("garbage").unwrap_err(),
+            "invalid coordinate: `garbage`"
+        );
+
+        let g1 = G1Affine("0x1".to_string(), "garbage".to_string());
+        assert!(g1.to_scrypt_string().is_err());
+        assert!(g1.to_packed_scrypt_string().is_err());
+    }
+}
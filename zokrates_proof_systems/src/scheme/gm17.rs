@@ -1,9 +1,13 @@
 use crate::scheme::{NonUniversalScheme, Scheme};
 use crate::solidity::{solidity_pairing_lib, SOLIDITY_G2_ADDITION_LIB};
-use crate::{G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme};
+use crate::{
+    G1Affine, G2Affine, SolidityCompatibleField, SolidityCompatibleScheme, SolidityExportConfig,
+};
 /* =============== add by sCrypt */
 use crate::scrypt::{scrypt_pairing_lib_bn128};
-use crate::{ScryptCompatibleField, ScryptCompatibleScheme};
+use crate::{
+    ScryptCompatibleField, ScryptCompatibleScheme, ScryptExportConfig, ScryptVerifierSections,
+};
 /* =============== end */
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -43,9 +47,12 @@ impl<T: Field> Scheme<T> for GM17 {
 impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for GM17 {
     type Proof = Self::ProofPoints;
 
-    fn export_solidity_verifier(vk: <GM17 as Scheme<T>>::VerificationKey) -> String {
+    fn export_solidity_verifier(
+        vk: <GM17 as Scheme<T>>::VerificationKey,
+        config: &SolidityExportConfig,
+    ) -> Result<String, String> {
         let (mut template_text, solidity_pairing_lib) =
-            (String::from(CONTRACT_TEMPLATE), solidity_pairing_lib(true));
+            (String::from(CONTRACT_TEMPLATE), solidity_pairing_lib(true, &config.precompile));
 
         // replace things in template
         let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
@@ -134,10 +141,10 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for GM17 {
         let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
         template_text = re.replace_all(&template_text, "uint256($v)").to_string();
 
-        format!(
+        Ok(format!(
             "{}{}{}",
             SOLIDITY_G2_ADDITION_LIB, solidity_pairing_lib, template_text
-        )
+        ))
     }
 }
 
@@ -210,14 +217,19 @@ contract Verifier {
 impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for GM17 {
     type Proof = Self::ProofPoints;
 
-    fn export_scrypt_verifier(_vk: <GM17 as Scheme<T>>::VerificationKey, curve_parameter: CurveParameter) -> String {
-        
-        let scrypt_pairing_lib = scrypt_pairing_lib_bn128();
+    fn export_scrypt_verifier_sections(
+        _vk: <GM17 as Scheme<T>>::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+    ) -> Result<ScryptVerifierSections, String> {
+        if config.domain_tag.is_some() {
+            return Err("Domain tags are only supported for g16".to_string());
+        }
 
-        format!(
-            "{}",
-            scrypt_pairing_lib
-        )
+        Ok(ScryptVerifierSections {
+            pairing_lib: config.resolve_imports(scrypt_pairing_lib_bn128()),
+            ..ScryptVerifierSections::default()
+        })
     }
 }
 
@@ -1,13 +1,26 @@
 use crate::scheme::{NonUniversalScheme, Scheme};
-use crate::solidity::solidity_pairing_lib;
-use crate::{G1Affine, G2Affine, MpcScheme, SolidityCompatibleField, SolidityCompatibleScheme, ToScryptString};
+use crate::solidity::{solidity_pairing_lib, solidity_pairing_lib_bls12_381};
+use crate::{
+    G1Affine, G2Affine, MpcScheme, SolidityCompatibleField, SolidityCompatibleScheme,
+    SolidityExportConfig, ToScryptString,
+};
 /* =============== add by sCrypt */
-use crate::scrypt::{scrypt_pairing_lib_bn128};
-use crate::{ScryptCompatibleField, ScryptCompatibleScheme};
+use crate::scrypt::{
+    scrypt_pair_check_bn128, scrypt_pairing_lib_bls12_381, scrypt_pairing_lib_bn128,
+};
+use crate::{
+    ScryptCompatibleField, ScryptCompatibleScheme, ScryptExportConfig, ScryptLanguage,
+    ScryptVerifierSections,
+};
 /* =============== end */
+use crate::{canonical_decimal, fq_to_biguint, Fq, G2AffineFq2, G2Order, ProofEncoding};
+use num_bigint::BigUint;
+use num_traits::Zero;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use zokrates_field::Field;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use zokrates_field::{Bls12_381Field, Field};
 use zokrates_common::helpers::{CurveParameter};
 
 
@@ -21,7 +34,7 @@ pub struct ProofPoints<G1, G2> {
     pub c: G1,
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct VerificationKey<G1, G2> {
     pub alpha: G1,
     pub beta: G2,
@@ -30,6 +43,152 @@ pub struct VerificationKey<G1, G2> {
     pub gamma_abc: Vec<G1>,
 }
 
+impl VerificationKey<G1Affine, G2Affine> {
+    /// SHA256 over the canonical decimal encoding of all constants of the key, so that
+    /// keys differing only in hex casing or `0x` prefixing share the same fingerprint
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        for c in self.coordinates() {
+            hasher.update(canonical_decimal(c));
+            hasher.update(",");
+        }
+        hex::encode(hasher.finalize())
+    }
+
+    fn coordinates(&self) -> Vec<&Fq> {
+        let mut coordinates = vec![&self.alpha.0, &self.alpha.1];
+        for g2 in [&self.beta, &self.gamma, &self.delta] {
+            coordinates.extend(g2.coordinates());
+        }
+        for g1 in &self.gamma_abc {
+            coordinates.push(&g1.0);
+            coordinates.push(&g1.1);
+        }
+        coordinates
+    }
+
+    /// Coordinates of the points of the key but the point at infinity, whose encoding by some
+    /// backends is not made of field elements
+    fn finite_coordinates(&self) -> Vec<&Fq> {
+        let mut coordinates = vec![];
+        if !self.alpha.is_infinity() {
+            coordinates.extend([&self.alpha.0, &self.alpha.1]);
+        }
+        for g2 in [&self.beta, &self.gamma, &self.delta] {
+            if !g2.is_infinity() {
+                coordinates.extend(g2.coordinates());
+            }
+        }
+        for g1 in self.gamma_abc.iter().filter(|g1| !g1.is_infinity()) {
+            coordinates.extend([&g1.0, &g1.1]);
+        }
+        coordinates
+    }
+
+    /// Checks that every coordinate of the key is an element of the base field of `curve`,
+    /// whose prime is hardcoded in the sCrypt pairing library of that curve
+    pub fn check_field_prime(&self, curve: CurveParameter, prime: &BigUint) -> Result<(), String> {
+        for c in self.finite_coordinates() {
+            let c_value = fq_to_biguint(c).map_err(|_| format!("invalid coordinate: `{}`", c))?;
+            if &c_value >= prime {
+                return Err(format!(
+                    "Coordinate {} is not an element of the {} base field, the verification key was generated for another curve",
+                    c, curve
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that every point of the key lies on the curve of `curve` and in its prime order
+    /// subgroup, naming the first offending field. The point at infinity is in every subgroup,
+    /// so it is skipped whatever its encoding. Only bn128 and bls12_381 are checked.
+    pub fn check_curve_points(&self, curve: CurveParameter) -> Result<(), String> {
+        let (g1, g2) = match curve {
+            CurveParameter::Bn128 => (CurveGroup::bn128_g1(), CurveGroup::bn128_g2()),
+            CurveParameter::Bls12_381 => (CurveGroup::bls12_381_g1(), CurveGroup::bls12_381_g2()),
+            _ => return Ok(()),
+        };
+
+        let check = |group: &CurveGroup, name: String, point: (Fq2Element, Fq2Element)| {
+            if !group.is_on_curve(&point) {
+                return Err(format!("`{}` is not a point of the {} curve", name, curve));
+            }
+            if !group.is_in_subgroup(&point) {
+                return Err(format!(
+                    "`{}` is not in the prime order subgroup of the {} curve",
+                    name, curve
+                ));
+            }
+            Ok(())
+        };
+        let g1_point = |g: &G1Affine| -> Result<(Fq2Element, Fq2Element), String> {
+            Ok((
+                (fq_to_biguint(&g.0)?, BigUint::zero()),
+                (fq_to_biguint(&g.1)?, BigUint::zero()),
+            ))
+        };
+        let g2_point = |g: &G2Affine| -> Result<(Fq2Element, Fq2Element), String> {
+            match g {
+                G2Affine::Fq2(G2AffineFq2(x, y)) => Ok((
+                    (fq_to_biguint(&x.0)?, fq_to_biguint(&x.1)?),
+                    (fq_to_biguint(&y.0)?, fq_to_biguint(&y.1)?),
+                )),
+                G2Affine::Fq(_) => Err("Expected a G2 point over Fq2".to_string()),
+            }
+        };
+
+        if !self.alpha.is_infinity() {
+            check(&g1, "alpha".to_string(), g1_point(&self.alpha)?)?;
+        }
+        for (name, g) in [("beta", &self.beta), ("gamma", &self.gamma), ("delta", &self.delta)] {
+            if !g.is_infinity() {
+                check(&g2, name.to_string(), g2_point(g)?)?;
+            }
+        }
+        for (i, g) in self.gamma_abc.iter().enumerate() {
+            if !g.is_infinity() {
+                check(&g1, format!("gamma_abc[{}]", i), g1_point(g)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Fixes the first public input of a BN128 key to `tag` by folding `tag * gamma_abc[1]`
+    /// into the constant term `gamma_abc[0]`. The resulting key only accepts proofs whose
+    /// first public input is `tag`, and takes the remaining public inputs.
+    pub fn with_domain_tag(&self, tag: &str) -> Result<Self, String> {
+        if self.gamma_abc.len() < 2 {
+            return Err("A domain tag requires at least one public input".to_string());
+        }
+        let tag = fq_to_biguint(tag)?;
+        if tag >= BigUint::parse_bytes(BN128_R.as_bytes(), 10).unwrap() {
+            return Err(format!("Domain tag must be smaller than {}", BN128_R));
+        }
+
+        // the curve arithmetic takes the point at infinity as `None`
+        let point = |g: &G1Affine| -> Result<Bn128G1, String> {
+            match g.is_infinity() {
+                true => Ok(None),
+                false => Ok(Some((fq_to_biguint(&g.0)?, fq_to_biguint(&g.1)?))),
+            }
+        };
+        let (x, y) = bn128_g1_add(
+            &point(&self.gamma_abc[0])?,
+            &bn128_g1_mul(&point(&self.gamma_abc[1])?, &tag),
+        )
+        .ok_or_else(|| "Domain tag folds the constant term to infinity".to_string())?;
+
+        let mut vk = self.clone();
+        vk.gamma_abc.remove(1);
+        vk.gamma_abc[0] = G1Affine(
+            format!("0x{:0>64}", x.to_str_radix(16)),
+            format!("0x{:0>64}", y.to_str_radix(16)),
+        );
+        Ok(vk)
+    }
+}
+
 impl<T: Field> Scheme<T> for G16 {
     const NAME: &'static str = "g16";
 
@@ -43,9 +202,16 @@ impl<T: Field> MpcScheme<T> for G16 {}
 impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
     type Proof = Self::ProofPoints;
 
-    fn export_solidity_verifier(vk: <G16 as Scheme<T>>::VerificationKey) -> String {
+    fn export_solidity_verifier(
+        vk: <G16 as Scheme<T>>::VerificationKey,
+        config: &SolidityExportConfig,
+    ) -> Result<String, String> {
+        if T::id() == Bls12_381Field::id() {
+            return export_solidity_verifier_bls12_381(vk);
+        }
+
         let (mut template_text, solidity_pairing_lib_sans_bn256g2) =
-            (String::from(CONTRACT_TEMPLATE_BN128), solidity_pairing_lib(false));
+            (String::from(CONTRACT_TEMPLATE_BN128), solidity_pairing_lib(false, &config.precompile));
 
         let vk_regex = Regex::new(r#"(<%vk_[^i%]*%>)"#).unwrap();
         let vk_gamma_abc_len_regex = Regex::new(r#"(<%vk_gamma_abc_length%>)"#).unwrap();
@@ -132,9 +298,145 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for G16 {
         let re = Regex::new(r"(?P<v>0[xX][0-9a-fA-F]{64})").unwrap();
         template_text = re.replace_all(&template_text, "uint256($v)").to_string();
 
-        format!("{}{}", solidity_pairing_lib_sans_bn256g2, template_text)
+        Ok(format!(
+            "{}{}",
+            solidity_pairing_lib_sans_bn256g2, template_text
+        ))
+    }
+}
+
+/// Solidity literal of a BLS12-381 base field element, see `solidity_pairing_lib_bls12_381`
+fn solidity_fp_bls12_381(e: &str) -> String {
+    let e = format!(
+        "{:0>96}",
+        e.trim_start_matches("0x").trim_start_matches("0X")
+    );
+    format!("Pairing.Fp(0x{}, 0x{})", &e[..32], &e[32..])
+}
+
+fn solidity_g1_bls12_381(g1: &G1Affine) -> String {
+    format!(
+        "Pairing.G1Point({}, {})",
+        solidity_fp_bls12_381(&g1.0),
+        solidity_fp_bls12_381(&g1.1)
+    )
+}
+
+fn solidity_g2_bls12_381(g2: &G2Affine) -> Result<String, String> {
+    match g2 {
+        G2Affine::Fq2(G2AffineFq2((x0, x1), (y0, y1))) => Ok(format!(
+            "Pairing.G2Point(Pairing.Fp2({}, {}), Pairing.Fp2({}, {}))",
+            solidity_fp_bls12_381(x0),
+            solidity_fp_bls12_381(x1),
+            solidity_fp_bls12_381(y0),
+            solidity_fp_bls12_381(y1)
+        )),
+        G2Affine::Fq(_) => Err("Expected a bls12_381 G2 point over Fq2".to_string()),
+    }
+}
+
+/// Exports a BLS12-381 verifier calling the EIP-2537 precompiles
+fn export_solidity_verifier_bls12_381(
+    vk: VerificationKey<G1Affine, G2Affine>,
+) -> Result<String, String> {
+    let input_count = vk
+        .gamma_abc
+        .len()
+        .checked_sub(1)
+        .ok_or_else(|| "The verification key has no gamma_abc points".to_string())?;
+
+    let gamma_abc_pts = vk
+        .gamma_abc
+        .iter()
+        .enumerate()
+        .map(|(i, g1)| format!("vk.gamma_abc[{}] = {};", i, solidity_g1_bls12_381(g1)))
+        .collect::<Vec<_>>()
+        .join("\n        ");
+
+    // take and feed input values only if there are any
+    let (input_argument, input_loop) = if input_count > 0 {
+        (
+            format!(", uint[{}] memory input", input_count),
+            r#"
+        for(uint i = 0; i < input.length; i++){
+            inputValues[i] = input[i];
+        }"#,
+        )
+    } else {
+        (String::new(), "")
+    };
+
+    let template_text = CONTRACT_TEMPLATE_BLS12_381
+        .replace("<%vk_alpha%>", &solidity_g1_bls12_381(&vk.alpha))
+        .replace("<%vk_beta%>", &solidity_g2_bls12_381(&vk.beta)?)
+        .replace("<%vk_gamma%>", &solidity_g2_bls12_381(&vk.gamma)?)
+        .replace("<%vk_delta%>", &solidity_g2_bls12_381(&vk.delta)?)
+        .replace("<%vk_gamma_abc_length%>", &vk.gamma_abc.len().to_string())
+        .replace("<%vk_gamma_abc_pts%>", &gamma_abc_pts)
+        .replace("<%vk_input_length%>", &input_count.to_string())
+        .replace("<%input_argument%>", &input_argument)
+        .replace("<%input_loop%>", input_loop);
+
+    Ok(format!(
+        "{}{}",
+        solidity_pairing_lib_bls12_381(),
+        template_text
+    ))
+}
+
+const CONTRACT_TEMPLATE_BLS12_381: &str = r#"
+contract Verifier {
+    using Pairing for *;
+    struct VerifyingKey {
+        Pairing.G1Point alpha;
+        Pairing.G2Point beta;
+        Pairing.G2Point gamma;
+        Pairing.G2Point delta;
+        Pairing.G1Point[] gamma_abc;
+    }
+    struct Proof {
+        Pairing.G1Point a;
+        Pairing.G2Point b;
+        Pairing.G1Point c;
+    }
+    function verifyingKey() pure internal returns (VerifyingKey memory vk) {
+        vk.alpha = <%vk_alpha%>;
+        vk.beta = <%vk_beta%>;
+        vk.gamma = <%vk_gamma%>;
+        vk.delta = <%vk_delta%>;
+        vk.gamma_abc = new Pairing.G1Point[](<%vk_gamma_abc_length%>);
+        <%vk_gamma_abc_pts%>
+    }
+    function verify(uint[] memory input, Proof memory proof) internal view returns (uint) {
+        uint256 snark_scalar_field = 52435875175126190479447740508185965837690552500527637822603658699938581184513;
+        VerifyingKey memory vk = verifyingKey();
+        require(input.length + 1 == vk.gamma_abc.length);
+        // Compute the linear combination vk_x
+        Pairing.G1Point memory vk_x = vk.gamma_abc[0];
+        for (uint i = 0; i < input.length; i++) {
+            require(input[i] < snark_scalar_field);
+            vk_x = Pairing.addition(vk_x, Pairing.scalar_mul(vk.gamma_abc[i + 1], input[i]));
+        }
+        if(!Pairing.pairingProd4(
+             proof.a, proof.b,
+             Pairing.negate(vk_x), vk.gamma,
+             Pairing.negate(proof.c), vk.delta,
+             Pairing.negate(vk.alpha), vk.beta)) return 1;
+        return 0;
+    }
+    function verifyTx(
+            Proof memory proof<%input_argument%>
+        ) public view returns (bool r) {
+        uint[] memory inputValues = new uint[](<%vk_input_length%>);
+        <%input_loop%>
+        if (verify(inputValues, proof) == 0) {
+            return true;
+        } else {
+            return false;
+        }
     }
 }
+"#;
 
 const CONTRACT_TEMPLATE_BN128: &str = r#"
 contract Verifier {
@@ -197,42 +499,86 @@ contract Verifier {
 impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for G16 {
     type Proof = Self::ProofPoints;
 
-    fn export_scrypt_verifier(vk: <G16 as Scheme<T>>::VerificationKey, curve_parameter: CurveParameter) -> String {
-        //let (mut verifier_template_text, mut zksnark_template_text, scrypt_pairing_bn256) =
-        //(String::from(SCRYPT_CONTRACT_TEMPLATE), String::from(ZKSNARK_TEMPLATE_BN128), scrypt_pairing_lib_bn128());
-        let mut zksnark_template_text: String;
-        let scrypt_pairing: String;
-
-        let vk_alpha_str: String;
-        let vk_beta_str: String;
-        let vk_gamma_str: String;
-        let vk_delta_str: String;
-
-        if curve_parameter == CurveParameter::Bn128 {
-            zksnark_template_text = String::from(ZKSNARK_TEMPLATE_BN128);
-            scrypt_pairing = scrypt_pairing_lib_bn128();
-            
-            vk_alpha_str = vk.alpha.to_scrypt_string();
-            vk_beta_str = vk.beta.to_scrypt_string();
-            vk_gamma_str = vk.gamma.to_scrypt_string();
-            vk_delta_str = vk.delta.to_scrypt_string();
-        } else {
-            // TODO
-            zksnark_template_text = "".to_owned();
-            scrypt_pairing = "".to_owned();
-
-            vk_alpha_str = "".to_owned();
-            vk_beta_str = "".to_owned();
-            vk_gamma_str = "".to_owned();
-            vk_delta_str = "".to_owned();
+    fn export_scrypt_verifier_sections(
+        vk: <G16 as Scheme<T>>::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+    ) -> Result<ScryptVerifierSections, String> {
+        config.check_contract_g2_order()?;
+
+        let vk = prepare_vk(vk, curve_parameter, config)?;
+
+        match curve_parameter {
+            CurveParameter::Bn128 => {}
+            CurveParameter::Bls12_381 => return export_scrypt_verifier_bls12_381(&vk, config),
+            CurveParameter::Bw6_761 => return export_scrypt_verifier_bw6_761(&vk, config),
+            curve => {
+                return Err(format!(
+                    "Could not export sCrypt verifier for curve {}: not supported",
+                    curve
+                ))
+            }
         }
 
+        let mut zksnark_template_text = String::from(ZKSNARK_TEMPLATE_BN128).replace(
+            "<%pair_check%>",
+            &scrypt_pair_check_bn128(
+                "vk.millerb1a1",
+                &[("a0", "<%b%>"), ("vk_x", "vk.gamma"), ("proof.c", "vk.delta")],
+                "<%subgroup_checks%>",
+            ),
+        );
+        let scrypt_pairing: Cow<str> = match &config.pairing_lib_import {
+            Some(module) => format!(
+                "import {{ FixedArray, SmartContractLib, method, prop }} from 'scrypt-ts'\nimport {{ BN256, BN256Pairing, FQ12, G1Point, G2Point{} }} from '{}'\n",
+                if config.packed_gamma_abc { ", PackedG1Point" } else { "" },
+                module
+            )
+            .into(),
+            None => scrypt_pairing_lib_bn128().into(),
+        };
+
+        let vk_alpha_str = vk.alpha.to_scrypt_string()?;
+        let vk_beta_str = vk.beta.with_order(config.g2_order).to_scrypt_string()?;
+        let vk_gamma_str = vk.gamma.with_order(config.g2_order).to_scrypt_string()?;
+        let vk_delta_str = vk.delta.with_order(config.g2_order).to_scrypt_string()?;
+
         let vk_regex = Regex::new(r#"(<%vk%>)"#).unwrap();
         let vk_gamma_abc_regex = Regex::new(r#"(<%vk_gamma_abc%>)"#).unwrap();
         let vk_gamma_abc_len_regex = Regex::new(r#"(<%vk_gamma_abc_length%>)"#).unwrap();
         let vk_input_len_regex = Regex::new(r#"(<%vk_input_length%>)"#).unwrap();
-        let input_loop = Regex::new(r#"(<%input_loop%>)"#).unwrap();
-        let input_argument = Regex::new(r#"(<%input_argument%>)"#).unwrap();
+        let subgroup_checks = Regex::new(r#"(<%subgroup_checks%>)"#).unwrap();
+
+        // reject proof points outside the prime order subgroups
+        zksnark_template_text = if config.subgroup_checks {
+            subgroup_checks.replace(
+                zksnark_template_text.as_str(),
+                r#"BN256.isInfCurvePoint(BN256.mulCurvePoint(BN256.createCurvePoint(proof.a), BN256.R)) &&
+            BN256.isInfTwistPoint(BN256.mulTwistPoint(BN256.createTwistPoint(<%b%>), BN256.R)) &&
+            BN256.isInfCurvePoint(BN256.mulCurvePoint(BN256.createCurvePoint(proof.c), BN256.R)) &&
+            "#,
+            )
+        } else {
+            subgroup_checks.replace(zksnark_template_text.as_str(), "")
+        }
+        .to_string();
+
+        // restore the ZoKrates component order of an EIP-197 encoded `proof.b` on-chain
+        let (proof_b, b) = match config.proof_encoding {
+            ProofEncoding::Scrypt => ("", "proof.b"),
+            ProofEncoding::Eip197 => (
+                r#"
+        // the proof encodes G2 in EIP-197 order, imaginary component first
+        const b: G2Point = {
+            x: { x: proof.b.x.y, y: proof.b.x.x },
+            y: { x: proof.b.y.y, y: proof.b.y.x },
+        }"#,
+                "b",
+            ),
+        };
+        zksnark_template_text = zksnark_template_text
+            .replace("<%proof_b%>", proof_b)
+            .replace("<%b%>", b);
 
         let gamma_abc_count: usize = vk.gamma_abc.len();
         
@@ -241,7 +587,11 @@ impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for G16 {
         let mut gamma_abc_repeat_text = String::new();
         gamma_abc_repeat_text.push_str("[");
         for (i, g1) in vk.gamma_abc.iter().enumerate() {
-            let to_add = g1.to_scrypt_string();
+            let to_add = if config.packed_gamma_abc {
+                g1.to_packed_scrypt_string()?
+            } else {
+                g1.to_scrypt_string_compact()?
+            };
             gamma_abc_repeat_text.push_str(
                 format!(
                     "{}",
@@ -261,6 +611,21 @@ impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for G16 {
         .replace(zksnark_template_text.as_str(), vk_gamma_abc_text.as_str())
         .into_owned();
 
+        // store gamma_abc as x coordinates and y parities, unpacked on-chain
+        let (gamma_abc_type, gamma_abc_0, gamma_abc_i) = if config.packed_gamma_abc {
+            (
+                "PackedG1Point",
+                "BN256.unpackG1Point(vk.gammaAbc[0])",
+                "BN256.unpackG1Point(vk.gammaAbc[i + 1])",
+            )
+        } else {
+            ("G1Point", "vk.gammaAbc[0]", "vk.gammaAbc[i + 1]")
+        };
+        zksnark_template_text = zksnark_template_text
+            .replace("<%gamma_abc_type%>", gamma_abc_type)
+            .replace("<%gamma_abc_0%>", gamma_abc_0)
+            .replace("<%gamma_abc_i%>", gamma_abc_i);
+
         let mut vk_repeat_text = String::new();
 
         vk_repeat_text.push_str("{\n alpha:");
@@ -303,19 +668,6 @@ impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for G16 {
         .replace(zksnark_template_text.as_str(), vk_repeat_text.as_str())
         .into_owned();
 
-
-        zksnark_template_text = if gamma_abc_count > 1 {
-            input_argument.replace(
-                zksnark_template_text.as_str(),
-                r#"int[ZKSNARK.N] inputs, "#,
-            )
-        } else {
-            input_argument.replace(zksnark_template_text.as_str(), "")
-        }
-        .to_string();
-
-
-
         zksnark_template_text = vk_gamma_abc_len_regex
             .replace_all(
                 zksnark_template_text.as_str(),
@@ -330,64 +682,793 @@ impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for G16 {
             )
             .into_owned();
 
+        check_scrypt_sizes(&zksnark_template_text)?;
+
+        // the verification key constants precede the types of the `SNARK` class
+        let split = zksnark_template_text
+            .find("export type VerifyingKey")
+            .unwrap_or(0);
+        let (verifying_key_data, snark_class) = zksnark_template_text.split_at(split);
+        check_scrypt_constants(
+            verifying_key_data,
+            &BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+        )?;
+
+        Ok(ScryptVerifierSections {
+            pairing_lib: config.resolve_imports(scrypt_pairing),
+            verifying_key_data: config.resolve_imports(verifying_key_data).into_owned(),
+            snark_class: config.resolve_imports(snark_class).into_owned(),
+            ..ScryptVerifierSections::default()
+        })
+    }
+
+    fn export_verifying_key_json(
+        vk: <G16 as Scheme<T>>::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+        miller_beta_alpha: &str,
+    ) -> Result<String, String> {
+        if curve_parameter != CurveParameter::Bn128 {
+            return Err(format!(
+                "Could not export sCrypt verifying key for curve {}: not supported",
+                curve_parameter
+            ));
+        }
 
-        // feed input values only if there are any
-        zksnark_template_text = if gamma_abc_count > 1 {
-            let repStr = if curve_parameter == CurveParameter::Bn128 {
-                r#"
+        let vk = prepare_vk(vk, curve_parameter, config)?;
+
+        let gamma_abc = vk
+            .gamma_abc
+            .iter()
+            .map(|g1| {
+                if config.packed_gamma_abc {
+                    g1.to_packed_scrypt_string()
+                } else {
+                    g1.to_scrypt_string_compact()
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let text = format!(
+            "{{\n alpha: {},\n beta: {},\n gamma: {},\n delta: {},\n gammaAbc: [{}],\n millerb1a1: {}\n }}",
+            vk.alpha.to_scrypt_string()?,
+            vk.beta.with_order(config.g2_order).to_scrypt_string()?,
+            vk.gamma.with_order(config.g2_order).to_scrypt_string()?,
+            vk.delta.with_order(config.g2_order).to_scrypt_string()?,
+            gamma_abc.join(","),
+            miller_beta_alpha
+        );
+        check_scrypt_constants(
+            &text,
+            &BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+        )?;
+
+        Ok(text)
+    }
+}
+
+/// Checks the coordinates of `vk` against the base field of the export curve and applies the
+/// domain tag of `config`
+fn prepare_vk(
+    vk: VerificationKey<G1Affine, G2Affine>,
+    curve_parameter: CurveParameter,
+    config: &ScryptExportConfig,
+) -> Result<VerificationKey<G1Affine, G2Affine>, String> {
+    match curve_parameter {
+        CurveParameter::Bn128 => {
+            vk.check_field_prime(
+                CurveParameter::Bn128,
+                &BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+            )?;
+            vk.check_curve_points(CurveParameter::Bn128)?
+        }
+        CurveParameter::Bls12_381 => {
+            vk.check_field_prime(
+                CurveParameter::Bls12_381,
+                &fq_to_biguint(BLS12_381_P).unwrap(),
+            )?;
+            vk.check_curve_points(CurveParameter::Bls12_381)?
+        }
+        CurveParameter::Bw6_761 => vk.check_field_prime(
+            CurveParameter::Bw6_761,
+            &fq_to_biguint(BW6_761_P).unwrap(),
+        )?,
+        _ => {}
+    }
+
+    let vk = match &config.domain_tag {
+        Some(tag) if curve_parameter == CurveParameter::Bn128 => vk.with_domain_tag(tag)?,
+        Some(_) => return Err("Domain tags are only supported on bn128".to_string()),
+        None => vk,
+    };
+
+    config.check_public_inputs(vk.gamma_abc.len().saturating_sub(1))?;
+
+    Ok(vk)
+}
+
+/// Exports a BLS12-381 verifier against the `BLS12381` sCrypt library, which is written in
+/// the sCrypt language rather than scrypt-ts, so the verifier is a `Verifier` contract in the
+/// sCrypt language as well. The verification key is embedded in the Montgomery form the
+/// library computes in, while proofs are taken in affine coordinates and converted on-chain.
+fn export_scrypt_verifier_bls12_381(
+    vk: &VerificationKey<G1Affine, G2Affine>,
+    config: &ScryptExportConfig,
+) -> Result<ScryptVerifierSections, String> {
+    if config.subgroup_checks || config.packed_gamma_abc || config.pairing_lib_import.is_some() {
+        return Err("Subgroup checks, packed gamma_abc and pairing library imports are only supported on bn128".to_string());
+    }
+    if config.proof_encoding != ProofEncoding::Scrypt {
+        return Err("Proof encodings other than scrypt are only supported on bn128".to_string());
+    }
+
+    let gamma_abc_count = vk.gamma_abc.len();
+    let gamma_abc = vk
+        .gamma_abc
+        .iter()
+        .map(bls12_381_g1_to_scrypt_string)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let vk_text = format!(
+        "{{\n        alpha: {},\n        beta: {},\n        gamma: {},\n        delta: {},\n        gammaAbc: [\n            {}\n        ]\n    }}",
+        bls12_381_g1_to_scrypt_string(&vk.alpha)?,
+        bls12_381_g2_to_scrypt_string(&vk.beta)?,
+        bls12_381_g2_to_scrypt_string(&vk.gamma)?,
+        bls12_381_g2_to_scrypt_string(&vk.delta)?,
+        gamma_abc.join(",\n            ")
+    );
+
+    // feed input values only if there are any
+    let (input_argument, input_value, input_loop) = if gamma_abc_count > 1 {
+        (
+            "int[N] inputs, ",
+            "inputs, ",
+            r#"
         loop (N) : i {
-            G1Point p = BN256.mulG1Point(
-                vk.gamma_abc[i + 1],
-                inputs[i]
-            );
-            vk_x = BN256.addG1Points(vk_x, p);
-        }"#
-            } else {
-                r#"
-            loop(N_1) : m {
-                vk.ic[m][k] = BLS12381.toMont(vk.ic[m][k]);
-            }"#
-            };
+            PointG1 p = BLS12381.MulScalarG1(vk.gammaAbc[i + 1], inputs[i]);
+            vk_x = BLS12381.AddG1(vk_x, p);
+        }"#,
+        )
+    } else {
+        ("", "", "")
+    };
+
+    let zksnark_template_text = ZKSNARK_TEMPLATE_BLS12_381
+        .replace("<%vk%>", &vk_text)
+        .replace("<%vk_gamma_abc_length%>", &gamma_abc_count.to_string())
+        .replace("<%vk_input_length%>", &(gamma_abc_count - 1).to_string())
+        .replace("<%input_argument%>", input_argument)
+        .replace("<%input_value%>", input_value)
+        .replace("<%input_loop%>", input_loop);
+
+    // the verification key constants precede the types of the `ZKSNARK` library
+    let split = zksnark_template_text
+        .find("struct VerifyingKey")
+        .unwrap_or(0);
+    let (verifying_key_data, snark_class) = zksnark_template_text.split_at(split);
+    check_scrypt_constants(verifying_key_data, &fq_to_biguint(BLS12_381_P).unwrap())?;
+
+    Ok(ScryptVerifierSections {
+        pairing_lib: scrypt_pairing_lib_bls12_381().into(),
+        verifying_key_data: verifying_key_data.to_string(),
+        snark_class: snark_class.to_string(),
+        language: ScryptLanguage::Scrypt,
+    })
+}
 
-            input_loop.replace(
-                &zksnark_template_text.as_str(), repStr,
-            )
-        } else {
-            input_loop.replace(zksnark_template_text.as_str(), "")
+/// Exports a BW6-761 verifier. No pairing library is embedded for this curve, so the
+/// verifier imports `BW6761` and `BW6761Pairing` from the `pairing_lib_import` module,
+/// which must provide `mulG1Point`, `addG1Points` and `pairCheckP4` over `G1Point` and
+/// a `G2Point` with coordinates in the base field.
+fn export_scrypt_verifier_bw6_761(
+    vk: &VerificationKey<G1Affine, G2Affine>,
+    config: &ScryptExportConfig,
+) -> Result<ScryptVerifierSections, String> {
+    let module = config.pairing_lib_import.as_ref().ok_or_else(|| {
+        "No sCrypt pairing library is embedded for bw6_761, the module of one must be imported"
+            .to_string()
+    })?;
+    if config.subgroup_checks || config.packed_gamma_abc {
+        return Err("Subgroup checks and packed gamma_abc are only supported on bn128".to_string());
+    }
+    if config.proof_encoding != ProofEncoding::Scrypt {
+        return Err("Proof encodings other than scrypt are only supported on bn128".to_string());
+    }
+
+    let pairing_lib = format!(
+        "import {{ FixedArray, SmartContractLib, method, prop }} from 'scrypt-ts'\nimport {{ BW6761, BW6761Pairing, G1Point, G2Point }} from '{}'\n",
+        module
+    );
+
+    let gamma_abc_count = vk.gamma_abc.len();
+    let gamma_abc = vk
+        .gamma_abc
+        .iter()
+        .map(|g1| g1.to_scrypt_string_compact())
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let vk_text = format!(
+        "{{\n alpha:{},\n beta:{},\n gamma: {},\n delta: {},\n gammaAbc: gammaAbc\n }}",
+        vk.alpha.to_scrypt_string()?,
+        vk.beta.to_scrypt_string()?,
+        vk.gamma.to_scrypt_string()?,
+        vk.delta.to_scrypt_string()?
+    );
+
+    let zksnark_template_text = ZKSNARK_TEMPLATE_BW6_761
+        .replace("<%vk_gamma_abc%>", &format!("[{}]", gamma_abc.join(",")))
+        .replace("<%vk%>", &vk_text)
+        .replace("<%vk_gamma_abc_length%>", &gamma_abc_count.to_string())
+        .replace("<%vk_input_length%>", &(gamma_abc_count - 1).to_string());
+
+    check_scrypt_sizes(&zksnark_template_text)?;
+
+    // the verification key constants precede the types of the `SNARK` class
+    let split = zksnark_template_text
+        .find("export type VerifyingKey")
+        .unwrap_or(0);
+    let (verifying_key_data, snark_class) = zksnark_template_text.split_at(split);
+    check_scrypt_constants(verifying_key_data, &fq_to_biguint(BW6_761_P).unwrap())?;
+
+    Ok(ScryptVerifierSections {
+        pairing_lib: config.resolve_imports(pairing_lib),
+        verifying_key_data: config.resolve_imports(verifying_key_data).into_owned(),
+        snark_class: config.resolve_imports(snark_class).into_owned(),
+        ..ScryptVerifierSections::default()
+    })
+}
+
+/// Modulus of the BW6-761 base field
+const BW6_761_P: &str = "0x122e824fb83ce0ad187c94004faff3eb926186a81d14688528275ef8087be41707ba638e584e91903cebaff25b423048689c8ed12f9fd9071dcd3dc73ebff2e98a116c25667a8f8160cf8aeeaf0a437e6913e6870000082f49d00000000008b";
+
+/// Modulus of the BLS12-381 base field
+const BLS12_381_P: &str = "0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab";
+
+/// Converts a BLS12-381 base field element to Montgomery form, as `BLS12381.toMont` does
+fn bls12_381_to_mont(e: &str) -> Result<String, String> {
+    let p = fq_to_biguint(BLS12_381_P).unwrap();
+    let mont = (fq_to_biguint(e)? << 384usize) % p;
+    Ok(format!("0x{}", mont.to_str_radix(16)))
+}
+
+/// Formats an affine G1 point as a `PointG1` in Montgomery form
+fn bls12_381_g1_to_scrypt_string(g1: &G1Affine) -> Result<String, String> {
+    // the library recognizes the point at infinity by its zero `z` coordinate
+    if g1.is_infinity() {
+        return Ok("[0, 0, 0]".to_string());
+    }
+    Ok(format!(
+        "[{}, {}, {}]",
+        bls12_381_to_mont(&g1.0)?,
+        bls12_381_to_mont(&g1.1)?,
+        bls12_381_to_mont("0x1")?
+    ))
+}
+
+/// Formats an affine G2 point as a `PointG2` in Montgomery form. The Fq2 components keep
+/// the order of the verification key, which is the `[c0, c1]` layout of `fe2`.
+fn bls12_381_g2_to_scrypt_string(g2: &G2Affine) -> Result<String, String> {
+    if g2.is_infinity() {
+        return Ok("[[0, 0], [0, 0], [0, 0]]".to_string());
+    }
+    match g2 {
+        G2Affine::Fq2(G2AffineFq2(x, y)) => Ok(format!(
+            "[[{}, {}], [{}, {}], [{}, 0]]",
+            bls12_381_to_mont(&x.0)?,
+            bls12_381_to_mont(&x.1)?,
+            bls12_381_to_mont(&y.0)?,
+            bls12_381_to_mont(&y.1)?,
+            bls12_381_to_mont("0x1")?
+        )),
+        G2Affine::Fq(_) => Err("Expected a G2 point over Fq2".to_string()),
+    }
+}
+
+/// Checks that the sizes substituted from `gamma_abc` into the sCrypt template agree
+/// with each other: every `FixedArray<G1Point, _>`, `N_PUB_INPUTS` and the number of
+/// points in the `gammaAbc` literal.
+fn check_scrypt_sizes(text: &str) -> Result<(), String> {
+    let fixed_array_regex = Regex::new(r"FixedArray<(?:Packed)?G1Point, (\d+)>").unwrap();
+    let n_pub_inputs_regex = Regex::new(r"N_PUB_INPUTS = (\d+)").unwrap();
+    let gamma_abc_regex =
+        Regex::new(r"(?s)const gammaAbc: [^=]*= \[(.*?)\]\s*export const VERIFYING_KEY_DATA")
+            .unwrap();
+    let point_regex = Regex::new(r"x:").unwrap();
+
+    let n_pub_inputs: usize = n_pub_inputs_regex
+        .captures(text)
+        .and_then(|c| c.get(1).unwrap().as_str().parse().ok())
+        .ok_or_else(|| "Inconsistent verifier: `N_PUB_INPUTS` not found".to_string())?;
+    let gamma_abc_length = n_pub_inputs + 1;
+
+    for c in fixed_array_regex.captures_iter(text) {
+        let length: usize = c.get(1).unwrap().as_str().parse().unwrap();
+        if length != gamma_abc_length {
+            return Err(format!(
+                "Inconsistent verifier: `{}` does not match `N_PUB_INPUTS = {}`",
+                c.get(0).unwrap().as_str(),
+                n_pub_inputs
+            ));
         }
-        .to_string();
+    }
 
+    let points = gamma_abc_regex
+        .captures(text)
+        .map(|c| point_regex.find_iter(c.get(1).unwrap().as_str()).count())
+        .ok_or_else(|| "Inconsistent verifier: `gammaAbc` not found".to_string())?;
+    if points != gamma_abc_length {
+        return Err(format!(
+            "Inconsistent verifier: `gammaAbc` has {} points but `N_PUB_INPUTS = {}`",
+            points, n_pub_inputs
+        ));
+    }
 
-        // feed input values only if there are any
-        zksnark_template_text = if gamma_abc_count > 1 {
-            input_argument.replace(
-                zksnark_template_text.as_str(),
-                r#"int[N] inputs, "#,
-            )
+    Ok(())
+}
+
+
+/// Checks that every numeric literal among the emitted verification key constants is a
+/// non-negative integer below the base field `modulus`, so that no sign error or unreduced
+/// value from the field element conversions reaches the contract
+pub(crate) fn check_scrypt_constants(text: &str, modulus: &BigUint) -> Result<(), String> {
+    let literal_regex = Regex::new(r"(-\s*)?\b(0[xX][0-9a-fA-F]+|[0-9]+)n?\b").unwrap();
+
+    for c in literal_regex.captures_iter(text) {
+        let literal = c.get(0).unwrap().as_str();
+        if c.get(1).is_some() {
+            return Err(format!("Invalid verifier: negative constant `{}`", literal));
+        }
+        let digits = c.get(2).unwrap().as_str();
+        let value = match digits.get(..2) {
+            Some("0x") | Some("0X") => fq_to_biguint(digits)?,
+            _ => BigUint::parse_bytes(digits.as_bytes(), 10).unwrap(),
+        };
+        if &value >= modulus {
+            return Err(format!(
+                "Invalid verifier: constant `{}` is not below the field modulus",
+                literal
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Modulus of the BN128 base field
+pub(crate) const BN128_P: &str =
+    "21888242871839275222246405745257275088696311157297823662689037894645226208583";
+
+fn decimal_to_hex(decimal: &str) -> Result<String, String> {
+    BigUint::parse_bytes(decimal.as_bytes(), 10)
+        .map(|n| format!("0x{:0>64}", n.to_str_radix(16)))
+        .ok_or_else(|| format!("Invalid field element `{}`", decimal))
+}
+
+/// Recovers the y coordinate of a BN128 G1 point from its x coordinate and the parity
+/// of y, as `BN256.unpackG1Point` does on-chain
+fn unpack_g1_y(x: &BigUint, y_odd: bool) -> BigUint {
+    let p = BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap();
+    let e = (&p + 1u32) / 4u32;
+    let y = ((x * x * x + 3u32) % &p).modpow(&e, &p);
+    if y.bit(0) != y_odd {
+        &p - y
+    } else {
+        y
+    }
+}
+
+/// Order of the BN128 scalar field
+pub(crate) const BN128_R: &str =
+    "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+
+/// Affine BN128 G1 point, `None` being the point at infinity
+type Bn128G1 = Option<(BigUint, BigUint)>;
+
+fn bn128_g1_add(a: &Bn128G1, b: &Bn128G1) -> Bn128G1 {
+    let ((x1, y1), (x2, y2)) = match (a, b) {
+        (None, _) => return b.clone(),
+        (_, None) => return a.clone(),
+        (Some(a), Some(b)) => (a, b),
+    };
+
+    let p = BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap();
+    let sub = |u: &BigUint, v: &BigUint| (u + &p - v) % &p;
+    let inv = |u: &BigUint| u.modpow(&(&p - 2u32), &p);
+
+    let lambda = if x1 == x2 {
+        if ((y1 + y2) % &p).is_zero() {
+            return None;
+        }
+        (x1 * x1 * 3u32 * inv(&(y1 + y1))) % &p
+    } else {
+        (sub(y2, y1) * inv(&sub(x2, x1))) % &p
+    };
+    let x3 = sub(&sub(&(&lambda * &lambda % &p), x1), x2);
+    let y3 = sub(&(&lambda * sub(x1, &x3) % &p), y1);
+    Some((x3, y3))
+}
+
+fn bn128_g1_mul(a: &Bn128G1, s: &BigUint) -> Bn128G1 {
+    (0..s.bits()).rev().fold(None, |acc, i| {
+        let acc = bn128_g1_add(&acc, &acc);
+        if s.bit(i) {
+            bn128_g1_add(&acc, a)
         } else {
-            input_argument.replace(zksnark_template_text.as_str(), "")
+            acc
         }
-        .to_string();
+    })
+}
 
+/// Order of the BLS12-381 scalar field
+const BLS12_381_R: &str = "0x73eda753299d7d483339d80809a1d80553bda402fffe5bfeffffffff00000001";
 
-        format!(
-            "{}{}",
-            scrypt_pairing, zksnark_template_text
+/// Element `c0 + c1 * u` of Fq2 = Fq[u] / (u^2 + 1), the quadratic extension of both the
+/// BN128 and the BLS12-381 base field. G1 coordinates are embedded with `c1 = 0`.
+type Fq2Element = (BigUint, BigUint);
+
+/// Short Weierstrass curve `y^2 = x^3 + b` over Fq2, with a prime order subgroup of order `r`
+struct CurveGroup {
+    p: BigUint,
+    b: Fq2Element,
+    r: BigUint,
+    /// Whether the curve has cofactor one, in which case every point is in the subgroup
+    prime_order: bool,
+}
+
+impl CurveGroup {
+    fn bn128_g1() -> Self {
+        CurveGroup {
+            p: BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+            b: (BigUint::from(3u32), BigUint::zero()),
+            r: BigUint::parse_bytes(BN128_R.as_bytes(), 10).unwrap(),
+            prime_order: true,
+        }
+    }
+
+    fn bn128_g2() -> Self {
+        let mut group = CurveGroup {
+            prime_order: false,
+            ..Self::bn128_g1()
+        };
+        // the twist coefficient is 3 / (9 + u)
+        group.b = group.mul(
+            &(BigUint::from(3u32), BigUint::zero()),
+            &group.inv(&(BigUint::from(9u32), BigUint::from(1u32))),
+        );
+        group
+    }
+
+    fn bls12_381_g1() -> Self {
+        CurveGroup {
+            p: fq_to_biguint(BLS12_381_P).unwrap(),
+            b: (BigUint::from(4u32), BigUint::zero()),
+            r: fq_to_biguint(BLS12_381_R).unwrap(),
+            prime_order: false,
+        }
+    }
+
+    fn bls12_381_g2() -> Self {
+        // the twist coefficient is 4 * (1 + u)
+        CurveGroup {
+            b: (BigUint::from(4u32), BigUint::from(4u32)),
+            ..Self::bls12_381_g1()
+        }
+    }
+
+    fn add(&self, a: &Fq2Element, b: &Fq2Element) -> Fq2Element {
+        ((&a.0 + &b.0) % &self.p, (&a.1 + &b.1) % &self.p)
+    }
+
+    fn sub(&self, a: &Fq2Element, b: &Fq2Element) -> Fq2Element {
+        (
+            (&a.0 + &self.p - &b.0) % &self.p,
+            (&a.1 + &self.p - &b.1) % &self.p,
         )
     }
+
+    fn mul(&self, a: &Fq2Element, b: &Fq2Element) -> Fq2Element {
+        let c0 = (&a.0 * &b.0 + &self.p * &self.p - (&a.1 * &b.1) % &self.p) % &self.p;
+        let c1 = (&a.0 * &b.1 + &a.1 * &b.0) % &self.p;
+        (c0, c1)
+    }
+
+    fn inv(&self, a: &Fq2Element) -> Fq2Element {
+        let norm = (&a.0 * &a.0 + &a.1 * &a.1) % &self.p;
+        let n = norm.modpow(&(&self.p - 2u32), &self.p);
+        ((&a.0 * &n) % &self.p, ((&self.p - &a.1) * &n) % &self.p)
+    }
+
+    fn is_zero(a: &Fq2Element) -> bool {
+        a.0.is_zero() && a.1.is_zero()
+    }
+
+    fn is_on_curve(&self, (x, y): &(Fq2Element, Fq2Element)) -> bool {
+        let lhs = self.mul(y, y);
+        let rhs = self.add(&self.mul(&self.mul(x, x), x), &self.b);
+        lhs == rhs
+    }
+
+    /// Whether `[r] point` is the point at infinity, computed in Jacobian coordinates to
+    /// avoid an inversion per step
+    fn is_in_subgroup(&self, point: &(Fq2Element, Fq2Element)) -> bool {
+        if self.prime_order {
+            return true;
+        }
+        (0..self.r.bits())
+            .rev()
+            .fold(None, |acc, i| {
+                let acc = self.double(acc);
+                if self.r.bit(i) {
+                    self.add_affine(acc, point)
+                } else {
+                    acc
+                }
+            })
+            .is_none()
+    }
+
+    /// Doubles a Jacobian point, `None` being the point at infinity
+    fn double(
+        &self,
+        a: Option<(Fq2Element, Fq2Element, Fq2Element)>,
+    ) -> Option<(Fq2Element, Fq2Element, Fq2Element)> {
+        let (x, y, z) = a?;
+        if Self::is_zero(&y) {
+            return None;
+        }
+        let xx = self.mul(&x, &x);
+        let yy = self.mul(&y, &y);
+        let yyyy = self.mul(&yy, &yy);
+        let x_yy = self.add(&x, &yy);
+        let d = self.sub(&self.mul(&x_yy, &x_yy), &self.add(&xx, &yyyy));
+        let d = self.add(&d, &d);
+        let e = self.add(&self.add(&xx, &xx), &xx);
+        let x3 = self.sub(&self.mul(&e, &e), &self.add(&d, &d));
+        let yyyy_2 = self.add(&yyyy, &yyyy);
+        let yyyy_4 = self.add(&yyyy_2, &yyyy_2);
+        let yyyy_8 = self.add(&yyyy_4, &yyyy_4);
+        let y3 = self.sub(&self.mul(&e, &self.sub(&d, &x3)), &yyyy_8);
+        let yz = self.mul(&y, &z);
+        let z3 = self.add(&yz, &yz);
+        Some((x3, y3, z3))
+    }
+
+    /// Adds an affine point to a Jacobian point, `None` being the point at infinity
+    fn add_affine(
+        &self,
+        a: Option<(Fq2Element, Fq2Element, Fq2Element)>,
+        (x2, y2): &(Fq2Element, Fq2Element),
+    ) -> Option<(Fq2Element, Fq2Element, Fq2Element)> {
+        let one = (BigUint::from(1u32), BigUint::zero());
+        let (x1, y1, z1) = match a {
+            None => return Some((x2.clone(), y2.clone(), one)),
+            Some(a) => a,
+        };
+        let z1z1 = self.mul(&z1, &z1);
+        let u2 = self.mul(x2, &z1z1);
+        let s2 = self.mul(&self.mul(y2, &z1), &z1z1);
+        let h = self.sub(&u2, &x1);
+        let r = self.sub(&s2, &y1);
+        if Self::is_zero(&h) {
+            return if Self::is_zero(&r) {
+                self.double(Some((x1, y1, z1)))
+            } else {
+                None
+            };
+        }
+        let hh = self.mul(&h, &h);
+        let hhh = self.mul(&h, &hh);
+        let v = self.mul(&x1, &hh);
+        let x3 = self.sub(&self.sub(&self.mul(&r, &r), &hhh), &self.add(&v, &v));
+        let y3 = self.sub(&self.mul(&r, &self.sub(&v, &x3)), &self.mul(&y1, &hhh));
+        let z3 = self.mul(&z1, &h);
+        Some((x3, y3, z3))
+    }
 }
 
+/// Parses the `VERIFYING_KEY_DATA` and `gammaAbc` constants of an exported BN128 sCrypt
+/// verifier back into a verification key, undoing the decimal encoding, the G2 `order`
+/// and the packing of `gammaAbc`.
+pub fn import_scrypt_verification_key(
+    text: &str,
+    order: G2Order,
+) -> Result<VerificationKey<G1Affine, G2Affine>, String> {
+    let vk_regex =
+        Regex::new(r"(?s)export const VERIFYING_KEY_DATA(?:: \w+)? = \{(.*?)gammaAbc: gammaAbc")
+            .unwrap();
+    let gamma_abc_regex = Regex::new(
+        r"(?s)const gammaAbc: FixedArray<(Packed)?G1Point, \d+> = \[(.*?)\]\s*export const VERIFYING_KEY_DATA",
+    )
+    .unwrap();
+    let number_regex = Regex::new(r"(\d+)n").unwrap();
+    let packed_regex = Regex::new(r"x: (\d+)n,\s*yOdd: (true|false)").unwrap();
+
+    let vk_numbers = vk_regex
+        .captures(text)
+        .map(|c| {
+            number_regex
+                .captures_iter(&c[1])
+                .map(|n| decimal_to_hex(&n[1]))
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .ok_or_else(|| "`VERIFYING_KEY_DATA` not found".to_string())??;
+    if vk_numbers.len() != 14 {
+        return Err(format!(
+            "Expected 14 field elements in `VERIFYING_KEY_DATA`, found {}",
+            vk_numbers.len()
+        ));
+    }
+
+    let g2 = |v: &[String]| {
+        G2Affine::Fq2(G2AffineFq2(
+            (v[0].clone(), v[1].clone()),
+            (v[2].clone(), v[3].clone()),
+        ))
+        .with_order(order)
+    };
+
+    let gamma_abc_captures = gamma_abc_regex
+        .captures(text)
+        .ok_or_else(|| "`gammaAbc` not found".to_string())?;
+    let gamma_abc = if gamma_abc_captures.get(1).is_some() {
+        packed_regex
+            .captures_iter(&gamma_abc_captures[2])
+            .map(|c| {
+                let x = BigUint::parse_bytes(c[1].as_bytes(), 10).unwrap();
+                let y = unpack_g1_y(&x, &c[2] == "true");
+                Ok(G1Affine(
+                    decimal_to_hex(&x.to_string())?,
+                    decimal_to_hex(&y.to_string())?,
+                ))
+            })
+            .collect::<Result<Vec<_>, String>>()?
+    } else {
+        let numbers = number_regex
+            .captures_iter(&gamma_abc_captures[2])
+            .map(|n| decimal_to_hex(&n[1]))
+            .collect::<Result<Vec<_>, _>>()?;
+        if numbers.len() % 2 != 0 {
+            return Err("Expected two coordinates for each `gammaAbc` point".to_string());
+        }
+        numbers
+            .chunks(2)
+            .map(|c| G1Affine(c[0].clone(), c[1].clone()))
+            .collect()
+    };
+
+    Ok(VerificationKey {
+        alpha: G1Affine(vk_numbers[0].clone(), vk_numbers[1].clone()),
+        beta: g2(&vk_numbers[2..6]),
+        gamma: g2(&vk_numbers[6..10]),
+        delta: g2(&vk_numbers[10..14]),
+        gamma_abc,
+    })
+}
 
 const ZKSNARK_TEMPLATE_BN128: &str = r#"
 
-const gammaAbc: FixedArray<G1Point, <%vk_gamma_abc_length%>> = <%vk_gamma_abc%>
+const gammaAbc: FixedArray<<%gamma_abc_type%>, <%vk_gamma_abc_length%>> = <%vk_gamma_abc%>
 
-export const VERIFYING_KEY_DATA = <%vk%>
+export const VERIFYING_KEY_DATA: VerifyingKeyData = <%vk%>
 
 export type VerifyingKey = {
     millerb1a1: FQ12 // Precalculated miller(alpha, beta)
     gamma: G2Point
     delta: G2Point
+    gammaAbc: FixedArray<<%gamma_abc_type%>, <%vk_gamma_abc_length%>> // Size of array should be N + 1
+}
+
+export type VerifyingKeyData = {
+    alpha: G1Point
+    beta: G2Point
+    gamma: G2Point
+    delta: G2Point
+    gammaAbc: FixedArray<<%gamma_abc_type%>, <%vk_gamma_abc_length%>>
+}
+
+export type Proof = {
+    a: G1Point
+    b: G2Point
+    c: G1Point
+}
+
+export const N_PUB_INPUTS = <%vk_input_length%>
+
+export class SNARK extends SmartContractLib {
+    @prop()
+    static readonly N: bigint = BigInt(N_PUB_INPUTS) // Number of public inputs.
+
+    @method()
+    static verify(
+        vk: VerifyingKey,
+        inputs: FixedArray<bigint, typeof N_PUB_INPUTS>,
+        proof: Proof,
+    ): boolean {
+        let vk_x = <%gamma_abc_0%>
+        for (let i = 0; i < N_PUB_INPUTS; i++) {
+            const p = BN256.mulG1Point(<%gamma_abc_i%>, inputs[i])
+            vk_x = BN256.addG1Points(vk_x, p)
+        }
+
+        const a0: G1Point = {
+            x: proof.a.x,
+            y: -proof.a.y,
+        }<%proof_b%>
+        <%pair_check%>
+    }
+}
+
+"#;
+
+const ZKSNARK_TEMPLATE_BLS12_381: &str = r#"
+
+library VerifyingKeyData {
+    static const VerifyingKey VK = <%vk%>;
+}
+
+struct VerifyingKey {
+    PointG1 alpha;
+    PointG2 beta;
+    PointG2 gamma;
+    PointG2 delta;
+    PointG1[<%vk_gamma_abc_length%>] gammaAbc; // Size of array should be N + 1
+}
+
+// Proof points in affine coordinates, as in proofs generated by ZoKrates
+struct Proof {
+    fe[2] a;
+    fp2 b;
+    fe[2] c;
+}
+
+library ZKSNARK {
+    static const int N = <%vk_input_length%>; // Number of public inputs.
+
+    static function toPointG1(fe[2] p) : PointG1 {
+        return [BLS12381.toMont(p[0]), BLS12381.toMont(p[1]), BLS12381.r1];
+    }
+
+    static function toPointG2(fp2 p) : PointG2 {
+        return [
+            [BLS12381.toMont(p[0][0]), BLS12381.toMont(p[0][1])],
+            [BLS12381.toMont(p[1][0]), BLS12381.toMont(p[1][1])],
+            [BLS12381.r1, 0]
+        ];
+    }
+
+    static function verify(VerifyingKey vk, <%input_argument%>Proof proof) : bool {
+        PointG1 vk_x = vk.gammaAbc[0];<%input_loop%>
+
+        return BLS12381Pairing.pairCheck4Point(
+            toPointG1(proof.a), toPointG2(proof.b),
+            vk.alpha, vk.beta,
+            vk_x, vk.gamma,
+            toPointG1(proof.c), vk.delta
+        );
+    }
+}
+
+contract Verifier {
+    static const int N = <%vk_input_length%>; // Number of public inputs.
+
+    public function unlock(<%input_argument%>Proof proof) {
+        require(ZKSNARK.verify(VerifyingKeyData.VK, <%input_value%>proof));
+    }
+}
+
+"#;
+
+const ZKSNARK_TEMPLATE_BW6_761: &str = r#"
+
+const gammaAbc: FixedArray<G1Point, <%vk_gamma_abc_length%>> = <%vk_gamma_abc%>
+
+export const VERIFYING_KEY_DATA: VerifyingKey = <%vk%>
+
+export type VerifyingKey = {
+    alpha: G1Point
+    beta: G2Point
+    gamma: G2Point
+    delta: G2Point
     gammaAbc: FixedArray<G1Point, <%vk_gamma_abc_length%>> // Size of array should be N + 1
 }
 
@@ -411,18 +1492,19 @@ export class SNARK extends SmartContractLib {
     ): boolean {
         let vk_x = vk.gammaAbc[0]
         for (let i = 0; i < N_PUB_INPUTS; i++) {
-            const p = BN256.mulG1Point(vk.gammaAbc[i + 1], inputs[i])
-            vk_x = BN256.addG1Points(vk_x, p)
+            const p = BW6761.mulG1Point(vk.gammaAbc[i + 1], inputs[i])
+            vk_x = BW6761.addG1Points(vk_x, p)
         }
 
         const a0: G1Point = {
             x: proof.a.x,
             y: -proof.a.y,
         }
-        return BN256Pairing.pairCheckP4Precalc(
+        return BW6761Pairing.pairCheckP4(
             a0,
             proof.b,
-            vk.millerb1a1,
+            vk.alpha,
+            vk.beta,
             vk_x,
             vk.gamma,
             proof.c,
@@ -432,3 +1514,889 @@ export class SNARK extends SmartContractLib {
 }
 
 "#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MAX_SCRYPT_PUBLIC_INPUTS;
+    use zokrates_field::Bn128Field;
+
+    /// Key whose points are the bn128 generators
+    fn vk() -> VerificationKey<G1Affine, G2Affine> {
+        let g1 = G1Affine("0x1".to_string(), "0x2".to_string());
+        let g2 = G2Affine::Fq2(crate::G2AffineFq2(
+            (
+                "0x1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed".to_string(),
+                "0x198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2".to_string(),
+            ),
+            (
+                "0x12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa".to_string(),
+                "0x090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b".to_string(),
+            ),
+        ));
+        VerificationKey {
+            alpha: g1.clone(),
+            beta: g2.clone(),
+            gamma: g2.clone(),
+            delta: g2,
+            gamma_abc: vec![g1.clone(), g1.clone(), g1],
+        }
+    }
+
+    /// Key whose points are the bls12_381 generators
+    fn bls12_381_vk() -> VerificationKey<G1Affine, G2Affine> {
+        let g1 = G1Affine(
+            "0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb".to_string(),
+            "0x08b3f481e3aaa0f1a09e30ed741d8ae4fcf5e095d5d00af600db18cb2c04b3edd03cc744a2888ae40caa232946c5e7e1".to_string(),
+        );
+        let g2 = G2Affine::Fq2(crate::G2AffineFq2(
+            (
+                "0x024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8".to_string(),
+                "0x13e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e".to_string(),
+            ),
+            (
+                "0x0ce5d527727d6e118cc9cdc6da2e351aadfd9baa8cbdd3a76d429a695160d12c923ac9cc3baca289e193548608b82801".to_string(),
+                "0x0606c4a02ea734cc32acd2b02bc28b99cb3e287e85a763af267492ab572e99ab3f370d275cec1da1aaa9075ff05f79be".to_string(),
+            ),
+        ));
+        VerificationKey {
+            alpha: g1.clone(),
+            beta: g2.clone(),
+            gamma: g2.clone(),
+            delta: g2,
+            gamma_abc: vec![g1.clone(), g1.clone(), g1],
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_hex_encoding() {
+        let mut other = vk();
+        other.alpha = G1Affine("1".to_string(), "0X02".to_string());
+        other.gamma_abc[2] = G1Affine("0x01".to_string(), "0x2".to_string());
+        assert_eq!(vk().fingerprint(), other.fingerprint());
+
+        other.alpha = G1Affine("0x3".to_string(), "0x2".to_string());
+        assert_ne!(vk().fingerprint(), other.fingerprint());
+    }
+
+    #[test]
+    fn scrypt_sizes_are_consistent() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+        assert!(verifier.contains("N_PUB_INPUTS = 2"));
+        assert!(check_scrypt_sizes(&verifier).is_ok());
+    }
+
+    #[test]
+    fn corrupted_scrypt_sizes_are_rejected() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+
+        let corrupted = verifier.replace("N_PUB_INPUTS = 2", "N_PUB_INPUTS = 3");
+        assert!(check_scrypt_sizes(&corrupted).is_err());
+
+        let corrupted = verifier.replacen("FixedArray<G1Point, 3>", "FixedArray<G1Point, 4>", 1);
+        assert!(check_scrypt_sizes(&corrupted).is_err());
+    }
+
+    #[test]
+    fn subgroup_checks_are_opt_in() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+        assert!(!verifier.contains("<%subgroup_checks%>"));
+        assert!(verifier.contains("return BN256Pairing.pairCheckP4Precalc("));
+        assert!(!verifier.contains("BN256.R))"));
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().subgroup_checks(true),
+        )
+        .unwrap();
+        assert!(verifier.contains("BN256.mulCurvePoint(BN256.createCurvePoint(proof.a), BN256.R)"));
+        assert!(verifier.contains("BN256.mulTwistPoint(BN256.createTwistPoint(proof.b), BN256.R)"));
+        assert!(verifier.contains("BN256.mulCurvePoint(BN256.createCurvePoint(proof.c), BN256.R)"));
+        assert!(verifier.contains("static mulTwistPoint(a: TwistPoint, m: bigint): TwistPoint"));
+    }
+
+    #[test]
+    fn eip197_proofs_are_swapped_on_chain() {
+        use zokrates_field::Bls12_381Field;
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+        assert!(verifier.contains("            a0,\n            proof.b,\n"));
+        assert!(!verifier.contains("const b: G2Point"));
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default()
+                .proof_encoding(ProofEncoding::Eip197)
+                .subgroup_checks(true),
+        )
+        .unwrap();
+        assert!(verifier.contains("x: { x: proof.b.x.y, y: proof.b.x.x },"));
+        assert!(verifier.contains("y: { x: proof.b.y.y, y: proof.b.y.x },"));
+        assert!(verifier.contains("            a0,\n            b,\n"));
+        assert!(verifier.contains("BN256.mulTwistPoint(BN256.createTwistPoint(b), BN256.R)"));
+        assert!(!verifier.contains("<%"));
+
+        assert!(
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier(
+                bls12_381_vk(),
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default().proof_encoding(ProofEncoding::Eip197),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn shared_pairing_lib_is_imported() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().pairing_lib_import("./bn256"),
+        )
+        .unwrap();
+        assert!(verifier.contains(
+            "import { BN256, BN256Pairing, FQ12, G1Point, G2Point } from './bn256'"
+        ));
+        assert!(!verifier.contains("export class BN256 "));
+        assert!(!verifier.contains("export class BN256Pairing "));
+        assert!(verifier.contains("export class SNARK extends SmartContractLib"));
+        assert!(check_scrypt_sizes(&verifier).is_ok());
+    }
+
+    #[test]
+    fn packed_gamma_abc_unpacks_to_original_points() {
+        use num_bigint::BigUint;
+
+        // G, -G, 2G and -2G
+        let points = vec![
+            G1Affine("0x1".to_string(), "0x2".to_string()),
+            G1Affine(
+                "0x1".to_string(),
+                "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45".to_string(),
+            ),
+            G1Affine(
+                "0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3".to_string(),
+                "0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4".to_string(),
+            ),
+            G1Affine(
+                "0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3".to_string(),
+                "0x1a76dae6d3272396d0cbe61fced2bc532edac647851e3ac53ce1cc9c7e645a83".to_string(),
+            ),
+        ];
+        let mut vk = vk();
+        vk.gamma_abc = points.clone();
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk,
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().packed_gamma_abc(true),
+        )
+        .unwrap();
+        assert!(verifier.contains("const gammaAbc: FixedArray<PackedG1Point, 4>"));
+        assert!(verifier.contains("let vk_x = BN256.unpackG1Point(vk.gammaAbc[0])"));
+        assert!(check_scrypt_sizes(&verifier).is_ok());
+
+        // mirror `BN256.unpackG1Point` with the constants embedded in the library
+        let constant = |name: &str| {
+            let re = Regex::new(&format!(r"static readonly {}: bigint =\s*(\d+)n", name)).unwrap();
+            BigUint::parse_bytes(re.captures(&verifier).unwrap()[1].as_bytes(), 10).unwrap()
+        };
+        let p = constant("P");
+        let e = constant("P_PLUS_1_DIV_4");
+        assert_eq!(e, (&p + 1u32) / 4u32);
+
+        let packed_regex = Regex::new(r"x: (\d+)n,\s*yOdd: (true|false)").unwrap();
+        let packed: Vec<_> = packed_regex.captures_iter(&verifier).collect();
+        assert_eq!(packed.len(), points.len());
+
+        for (c, point) in packed.iter().zip(points) {
+            let x = BigUint::parse_bytes(c[1].as_bytes(), 10).unwrap();
+            let y_odd = &c[2] == "true";
+
+            let mut y = ((&x * &x * &x + 3u32) % &p).modpow(&e, &p);
+            if y.bit(0) != y_odd {
+                y = &p - y;
+            }
+
+            assert_eq!(x.to_string(), canonical_decimal(&point.0));
+            assert_eq!(y.to_string(), canonical_decimal(&point.1));
+        }
+    }
+
+    #[test]
+    fn import_reverses_export() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+
+        let imported = import_scrypt_verification_key(&verifier, G2Order::Scrypt).unwrap();
+        assert_eq!(imported.fingerprint(), vk().fingerprint());
+        assert_eq!(imported.alpha.0, format!("0x{:0>64}", "1"));
+
+        // importing with the wrong order swaps the G2 components
+        let imported = import_scrypt_verification_key(&verifier, G2Order::Standard).unwrap();
+        assert_ne!(imported.fingerprint(), vk().fingerprint());
+    }
+
+    #[test]
+    fn standard_g2_order_is_rejected_for_contracts() {
+        let config = ScryptExportConfig::default().g2_order(G2Order::Standard);
+        assert!(
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk(),
+                CurveParameter::Bn128,
+                &config,
+            )
+            .unwrap_err()
+            .contains("only supported for verifying key exports")
+        );
+
+        let key = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_verifying_key_json(
+            vk(),
+            CurveParameter::Bn128,
+            &config,
+            "{}",
+        )
+        .unwrap();
+        let standard = vk().beta.with_order(G2Order::Standard);
+        assert!(key.contains(&standard.to_scrypt_string().unwrap()));
+    }
+
+    #[test]
+    fn import_unpacks_gamma_abc() {
+        let mut vk = vk();
+        vk.gamma_abc = vec![
+            G1Affine("0x1".to_string(), "0x2".to_string()),
+            G1Affine(
+                "0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3".to_string(),
+                "0x1a76dae6d3272396d0cbe61fced2bc532edac647851e3ac53ce1cc9c7e645a83".to_string(),
+            ),
+        ];
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk.clone(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().packed_gamma_abc(true),
+        )
+        .unwrap();
+
+        let imported = import_scrypt_verification_key(&verifier, G2Order::Scrypt).unwrap();
+        assert_eq!(imported.fingerprint(), vk.fingerprint());
+    }
+
+    #[test]
+    fn sections_join_to_verifier() {
+        let config = ScryptExportConfig::default();
+        let sections =
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier_sections(
+                vk(),
+                CurveParameter::Bn128,
+                &config,
+            )
+            .unwrap();
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &config,
+        )
+        .unwrap();
+        assert_eq!(sections.join(), verifier);
+
+        assert!(sections.pairing_lib.contains("export class BN256Pairing"));
+        assert!(!sections.pairing_lib.contains("VERIFYING_KEY_DATA"));
+        // the embedded library is not copied
+        assert!(matches!(sections.pairing_lib, Cow::Borrowed(_)));
+        assert!(sections.verifying_key_data.contains("const gammaAbc"));
+        assert!(sections.verifying_key_data.contains("export const VERIFYING_KEY_DATA"));
+        assert!(!sections.verifying_key_data.contains("export class SNARK"));
+        assert!(sections.snark_class.starts_with("export type VerifyingKey"));
+        assert!(sections.snark_class.contains("export class SNARK"));
+
+        assert!(!verifier.contains('\r'));
+        assert!(verifier.ends_with('\n'));
+        assert!(!verifier.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn domain_tag_folds_first_input() {
+        let g = G1Affine("0x1".to_string(), "0x2".to_string());
+        let mut vk = vk();
+        vk.gamma_abc = vec![g.clone(), g.clone(), g];
+
+        // G + 1 * G = 2G
+        let tagged = vk.with_domain_tag("0x1").unwrap();
+        assert_eq!(tagged.gamma_abc.len(), 2);
+        assert_eq!(
+            canonical_decimal(&tagged.gamma_abc[0].0),
+            canonical_decimal("0x030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3")
+        );
+        assert_eq!(
+            canonical_decimal(&tagged.gamma_abc[0].1),
+            canonical_decimal("0x15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4")
+        );
+
+        // G + 0 * G = G
+        let tagged = vk.with_domain_tag("0x0").unwrap();
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].0), "1");
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].1), "2");
+
+        // G + (r - 1) * G = infinity
+        let r = BigUint::parse_bytes(BN128_R.as_bytes(), 10).unwrap();
+        assert!(vk.with_domain_tag(&(&r - 1u32).to_str_radix(16)).is_err());
+        assert!(vk.with_domain_tag(&r.to_str_radix(16)).is_err());
+        assert!(vk.with_domain_tag("0xbug").is_err());
+
+        // gamma_abc points at infinity, as ark encodes them, are the neutral element
+        let infinity = G1Affine("0x0".to_string(), "0x1".to_string());
+        let mut sparse = vk.clone();
+        sparse.gamma_abc[1] = infinity.clone();
+        let tagged = sparse.with_domain_tag("0x5").unwrap();
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].0), "1");
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].1), "2");
+
+        sparse.gamma_abc = vec![infinity, vk.gamma_abc[1].clone(), vk.gamma_abc[2].clone()];
+        let tagged = sparse.with_domain_tag("0x1").unwrap();
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].0), "1");
+        assert_eq!(canonical_decimal(&tagged.gamma_abc[0].1), "2");
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk,
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().domain_tag("0x1"),
+        )
+        .unwrap();
+        assert!(verifier.contains("N_PUB_INPUTS = 1"));
+    }
+
+    #[test]
+    fn export_solidity_verifier_bls12_381() {
+        let verifier = <G16 as SolidityCompatibleScheme<Bls12_381Field>>::export_solidity_verifier(
+            bls12_381_vk(),
+            &SolidityExportConfig::default(),
+        )
+        .unwrap();
+
+        assert!(verifier.contains("address(0x0f).staticcall(input)"));
+        assert!(verifier.contains(
+            "vk.alpha = Pairing.G1Point(Pairing.Fp(0x17f1d3a73197d7942695638c4fa9ac0f, 0xc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb), "
+        ));
+        assert!(verifier.contains("vk.gamma_abc = new Pairing.G1Point[](3);"));
+        assert!(verifier.contains("vk.gamma_abc[2] = Pairing.G1Point("));
+        assert!(verifier.contains("Proof memory proof, uint[2] memory input"));
+        assert!(!verifier.contains("<%"));
+        assert!(!verifier.contains("uint256(0x"));
+
+        let mut vk = bls12_381_vk();
+        vk.gamma_abc.clear();
+        assert!(
+            <G16 as SolidityCompatibleScheme<Bls12_381Field>>::export_solidity_verifier(
+                vk,
+                &SolidityExportConfig::default(),
+            )
+            .is_err()
+        );
+
+        let mut vk = bls12_381_vk();
+        vk.beta = G2Affine::Fq(crate::G2AffineFq("0x1".to_string(), "0x2".to_string()));
+        assert!(
+            <G16 as SolidityCompatibleScheme<Bls12_381Field>>::export_solidity_verifier(
+                vk,
+                &SolidityExportConfig::default(),
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn too_many_public_inputs() {
+        let mut vk = vk();
+        vk.gamma_abc = vec![vk.alpha.clone(); MAX_SCRYPT_PUBLIC_INPUTS + 2];
+
+        let res = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk.clone(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        );
+        assert!(res.unwrap_err().contains("aggregating"));
+
+        // the limit is configurable, and a domain tag takes an input off the verifier
+        let config = ScryptExportConfig::default().max_public_inputs(MAX_SCRYPT_PUBLIC_INPUTS + 1);
+        assert!(<G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk.clone(),
+            CurveParameter::Bn128,
+            &config,
+        )
+        .is_ok());
+        assert!(<G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk,
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().domain_tag("0x1"),
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn scrypt_ts_import_is_substituted() {
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default().scrypt_ts_import("@vendor/scrypt-ts"),
+        )
+        .unwrap();
+        assert!(verifier.contains(
+            "import { and, SmartContractLib, method, lshift, prop, FixedArray } from '@vendor/scrypt-ts'"
+        ));
+        assert!(!verifier.contains("from 'scrypt-ts'"));
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default()
+                .scrypt_ts_import("@vendor/scrypt-ts")
+                .pairing_lib_import("./bn256"),
+        )
+        .unwrap();
+        assert!(verifier.contains(
+            "import { FixedArray, SmartContractLib, method, prop } from '@vendor/scrypt-ts'"
+        ));
+        assert!(!verifier.contains("from 'scrypt-ts'"));
+    }
+
+    #[test]
+    fn bls12_381_montgomery_form() {
+        // the Montgomery form of one is the `r1` constant of the `BLS12381` library
+        assert_eq!(
+            bls12_381_to_mont("0x1").unwrap(),
+            "0x15f65ec3fa80e4935c071a97a256ec6d77ce5853705257455f48985753c758baebf4000bc40c0002760900000002fffd"
+        );
+        assert_eq!(bls12_381_to_mont("0x0").unwrap(), "0x0");
+        assert!(bls12_381_to_mont("0xzz").is_err());
+    }
+
+    #[test]
+    fn export_bls12_381_verifier() {
+        use zokrates_field::Bls12_381Field;
+
+        let sections =
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier_sections(
+                bls12_381_vk(),
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+        assert_eq!(sections.pairing_lib, scrypt_pairing_lib_bls12_381());
+        assert!(sections.verifying_key_data.contains("library VerifyingKeyData"));
+        assert!(sections.snark_class.contains("PointG1[3] gammaAbc;"));
+        assert!(sections.snark_class.contains("static const int N = 2;"));
+        assert!(sections
+            .snark_class
+            .contains("verify(VerifyingKey vk, int[N] inputs, Proof proof)"));
+        assert!(sections.snark_class.contains("BLS12381Pairing.pairCheck4Point("));
+        assert!(sections
+            .snark_class
+            .contains("public function unlock(int[N] inputs, Proof proof)"));
+        assert!(sections
+            .snark_class
+            .contains("require(ZKSNARK.verify(VerifyingKeyData.VK, inputs, proof));"));
+        assert_eq!(sections.language, ScryptLanguage::Scrypt);
+        assert!(!sections.join().contains("scrypt-ts"));
+
+        let g2 = match bls12_381_vk().beta {
+            G2Affine::Fq2(G2AffineFq2(x, y)) => format!(
+                "[[{}, {}], [{}, {}], [{}, 0]]",
+                bls12_381_to_mont(&x.0).unwrap(),
+                bls12_381_to_mont(&x.1).unwrap(),
+                bls12_381_to_mont(&y.0).unwrap(),
+                bls12_381_to_mont(&y.1).unwrap(),
+                bls12_381_to_mont("0x1").unwrap()
+            ),
+            G2Affine::Fq(_) => unreachable!(),
+        };
+        assert!(sections.verifying_key_data.contains(&format!("beta: {}", g2)));
+        assert!(!sections.verifying_key_data.contains("<%"));
+        assert!(!sections.snark_class.contains("<%"));
+    }
+
+    #[test]
+    fn bls12_381_rejects_bn128_options() {
+        use zokrates_field::Bls12_381Field;
+
+        let res = <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier(
+            bls12_381_vk(),
+            CurveParameter::Bls12_381,
+            &ScryptExportConfig::default().subgroup_checks(true),
+        );
+        assert!(res.is_err());
+    }
+
+    #[test]
+    fn scrypt_constants_are_validated() {
+        let bn128_p = BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap();
+        let bls12_381_p = fq_to_biguint(BLS12_381_P).unwrap();
+        let bw6_761_p = fq_to_biguint(BW6_761_P).unwrap();
+        assert!(check_scrypt_constants(
+            "x: 21888242871839275222246405745257275088696311157297823662689037894645226208582n",
+            &bn128_p
+        )
+        .is_ok());
+        assert!(check_scrypt_constants("[0x1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaaa, 0]", &bls12_381_p).is_ok());
+        assert!(check_scrypt_constants("x: -5n", &bn128_p).is_err());
+        assert!(check_scrypt_constants("[- 0x5, 0]", &bls12_381_p).is_err());
+        assert!(check_scrypt_constants(&format!("x: 0x1{}", "0".repeat(64)), &bn128_p).is_err());
+
+        // values that fit the bit size of the field but are not reduced are rejected
+        assert!(check_scrypt_constants(&format!("x: {}n", BN128_P), &bn128_p).is_err());
+        assert!(check_scrypt_constants(&format!("x: 0x3{}", "f".repeat(63)), &bn128_p).is_err());
+        assert!(check_scrypt_constants(&format!("[{}, 0]", BLS12_381_P), &bls12_381_p).is_err());
+        assert!(check_scrypt_constants(&format!("{{ x: {} }}", BW6_761_P), &bw6_761_p).is_err());
+
+        // a negative constant injected into an exported verifier is caught
+        let sections = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier_sections(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap();
+        assert!(check_scrypt_constants(&sections.verifying_key_data, &bn128_p).is_ok());
+        let injected = sections.verifying_key_data.replacen("x: ", "x: -", 1);
+        assert!(check_scrypt_constants(&injected, &bn128_p).is_err());
+    }
+
+    #[test]
+    fn invalid_coordinates_are_reported() {
+        let mut vk = vk();
+        vk.alpha = G1Affine("1".to_string(), "2".to_string());
+        assert!(<G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk.clone(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .is_ok());
+
+        vk.alpha = G1Affine("0x1".to_string(), "0xnope".to_string());
+        assert_eq!(
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap_err(),
+            "invalid coordinate: `0xnope`"
+        );
+    }
+
+    #[test]
+    fn deserialize_array_and_object_points() {
+        let arrays = r#"{
+            "alpha": ["0x1", "0x2"],
+            "beta": [["0x1", "0x2"], ["0x3", "0x4"]],
+            "gamma": [["0x1", "0x2"], ["0x3", "0x4"]],
+            "delta": [["0x1", "0x2"], ["0x3", "0x4"]],
+            "gamma_abc": [["0x1", "0x2"], ["0x1", "0x2"], ["0x1", "0x2"]]
+        }"#;
+        let objects = r#"{
+            "alpha": { "x": "0x1", "y": "0x2" },
+            "beta": { "x": ["0x1", "0x2"], "y": ["0x3", "0x4"] },
+            "gamma": { "x": ["0x1", "0x2"], "y": ["0x3", "0x4"] },
+            "delta": { "x": ["0x1", "0x2"], "y": ["0x3", "0x4"] },
+            "gamma_abc": [
+                { "x": "0x1", "y": "0x2" },
+                ["0x1", "0x2"],
+                { "x": "0x1", "y": "0x2" }
+            ]
+        }"#;
+
+        let g1 = G1Affine("0x1".to_string(), "0x2".to_string());
+        let g2 = G2Affine::Fq2(crate::G2AffineFq2(
+            ("0x1".to_string(), "0x2".to_string()),
+            ("0x3".to_string(), "0x4".to_string()),
+        ));
+        let expected = serde_json::to_value(VerificationKey {
+            alpha: g1.clone(),
+            beta: g2.clone(),
+            gamma: g2.clone(),
+            delta: g2,
+            gamma_abc: vec![g1.clone(), g1.clone(), g1],
+        })
+        .unwrap();
+        for json in [arrays, objects] {
+            let vk: VerificationKey<G1Affine, G2Affine> = serde_json::from_str(json).unwrap();
+            assert_eq!(serde_json::to_value(vk).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn reject_coordinates_outside_the_base_field() {
+        // a coordinate of a BLS12-381 key, larger than the BN128 prime
+        let mut vk = vk();
+        vk.gamma_abc[1] = G1Affine(
+            "0x17f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb".to_string(),
+            "0x2".to_string(),
+        );
+
+        assert!(vk
+            .check_field_prime(
+                CurveParameter::Bls12_381,
+                &fq_to_biguint(BLS12_381_P).unwrap()
+            )
+            .is_ok());
+        assert!(<G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+            vk,
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+        )
+        .unwrap_err()
+        .contains("not an element of the bn128 base field"));
+
+        // a non-canonical coordinate, p + 2 for y = 2, is rejected rather than reduced
+        let p = BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap();
+        let mut vk = vk();
+        vk.alpha.1 = format!("0x{}", (p + 2u32).to_str_radix(16));
+        assert!(
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap_err()
+            .contains("not an element of the bn128 base field")
+        );
+    }
+
+    #[test]
+    fn export_bw6_761_verifier() {
+        use zokrates_field::Bw6_761Field;
+
+        let mut vk = vk();
+        let g2 = G2Affine::Fq(crate::G2AffineFq("0x3".to_string(), "0x4".to_string()));
+        vk.beta = g2.clone();
+        vk.gamma = g2.clone();
+        vk.delta = g2;
+
+        // no pairing library is embedded for bw6_761
+        assert!(<G16 as ScryptCompatibleScheme<Bw6_761Field>>::export_scrypt_verifier(
+            vk.clone(),
+            CurveParameter::Bw6_761,
+            &ScryptExportConfig::default(),
+        )
+        .is_err());
+
+        let verifier = <G16 as ScryptCompatibleScheme<Bw6_761Field>>::export_scrypt_verifier(
+            vk,
+            CurveParameter::Bw6_761,
+            &ScryptExportConfig::default().pairing_lib_import("./bw6_761"),
+        )
+        .unwrap();
+        assert!(verifier.contains(
+            "import { BW6761, BW6761Pairing, G1Point, G2Point } from './bw6_761'"
+        ));
+        assert!(verifier.contains("beta:{\n                x: 3n,\n                y: 4n\n            }"));
+        assert!(verifier.contains("BW6761Pairing.pairCheckP4("));
+        assert!(!verifier.contains("<%"));
+        assert!(check_scrypt_sizes(&verifier).is_ok());
+    }
+
+    /// Checks the declarations `tsc --strict` would reject: module level constants must be
+    /// typed or initialized with a literal, and every declared type must be exported
+    fn assert_strict_declarations(verifier: &str) {
+        let untyped_const = Regex::new(r"(?m)^(?:export )?const (\w+) = [^\d]").unwrap();
+        assert!(
+            !untyped_const.is_match(verifier),
+            "untyped constant in {}",
+            verifier
+        );
+        let private_type = Regex::new(r"(?m)^type ").unwrap();
+        assert!(!private_type.is_match(verifier));
+        for referenced in ["VerifyingKeyData", "VerifyingKey", "Proof"] {
+            if verifier.contains(&format!(": {}", referenced)) {
+                assert!(verifier.contains(&format!("export type {} = ", referenced)));
+            }
+        }
+    }
+
+    #[test]
+    fn verifier_declarations_are_strict() {
+        for config in [
+            ScryptExportConfig::default(),
+            ScryptExportConfig::default().packed_gamma_abc(true),
+            ScryptExportConfig::default().pairing_lib_import("./bn256"),
+        ] {
+            let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk(),
+                CurveParameter::Bn128,
+                &config,
+            )
+            .unwrap();
+            assert!(verifier.contains("export const VERIFYING_KEY_DATA: VerifyingKeyData = {"));
+            assert_strict_declarations(&verifier);
+
+            // the annotated key is still recognized on import
+            assert!(import_scrypt_verification_key(&verifier, G2Order::Scrypt).is_ok());
+        }
+    }
+
+    #[test]
+    fn export_verifying_key_alone() {
+        let miller = crate::fq12_to_scrypt_string(&vec!["7".to_string(); 12]);
+
+        let key = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_verifying_key_json(
+            vk(),
+            CurveParameter::Bn128,
+            &ScryptExportConfig::default(),
+            &miller,
+        )
+        .unwrap();
+        assert!(key.starts_with("{\n alpha: {"));
+        assert!(key.contains("gammaAbc: [{"));
+        assert!(key.contains(&format!("millerb1a1: {}", miller)));
+        assert!(!key.contains("SmartContractLib"));
+        assert!(!key.contains("import"));
+
+        assert!(
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_verifying_key_json(
+                vk(),
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+                &miller,
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn export_infinity_in_gamma_abc() {
+        let zero = format!("0x{:0>64}", "");
+        let one = format!("0x{:0>64}", "1");
+
+        // as written by ark, then by bellman
+        for infinity in [
+            G1Affine(zero.clone(), one.clone()),
+            G1Affine(zero.clone(), zero.clone()),
+        ] {
+            let mut vk = vk();
+            vk.gamma_abc[1] = infinity;
+            let verifier = <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+            assert!(verifier.contains("[{ x: 1n, y: 2n },{ x: 0n, y: 0n },{ x: 1n, y: 2n }]"));
+        }
+    }
+
+    #[test]
+    fn infinity_passes_key_checks() {
+        // as written by ark on bn128
+        let mut vk = vk();
+        vk.gamma_abc[1] = G1Affine(format!("0x{:0>64}", ""), format!("0x{:0>64}", "1"));
+        vk.check_field_prime(
+            CurveParameter::Bn128,
+            &BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+        )
+        .unwrap();
+        vk.check_curve_points(CurveParameter::Bn128).unwrap();
+
+        // as written by bellman on bls12_381, flagged in the top byte of `x`
+        let mut vk = bls12_381_vk();
+        vk.gamma_abc[1] = G1Affine(format!("0x40{:0>94}", ""), format!("0x{:0>96}", ""));
+        vk.check_field_prime(CurveParameter::Bls12_381, &fq_to_biguint(BLS12_381_P).unwrap())
+            .unwrap();
+        vk.check_curve_points(CurveParameter::Bls12_381).unwrap();
+
+        let sections =
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier_sections(
+                vk,
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+        assert!(sections.verifying_key_data.contains("[0, 0, 0]"));
+
+        // a flagged coordinate of a finite point is still rejected
+        let mut vk = bls12_381_vk();
+        vk.gamma_abc[1].0 = format!("0x40{:0>94}", "1");
+        assert!(vk
+            .check_field_prime(CurveParameter::Bls12_381, &fq_to_biguint(BLS12_381_P).unwrap())
+            .is_err());
+    }
+
+    #[test]
+    fn reject_points_off_the_curve() {
+        use zokrates_field::Bls12_381Field;
+
+        assert!(vk().check_curve_points(CurveParameter::Bn128).is_ok());
+        assert!(bls12_381_vk()
+            .check_curve_points(CurveParameter::Bls12_381)
+            .is_ok());
+
+        // a single corrupted limb of alpha
+        let mut vk = vk();
+        vk.alpha.1 = "0x3".to_string();
+        assert_eq!(
+            <G16 as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap_err(),
+            "`alpha` is not a point of the bn128 curve"
+        );
+
+        let mut vk = bls12_381_vk();
+        vk.alpha.1 = "0x3".to_string();
+        assert_eq!(
+            <G16 as ScryptCompatibleScheme<Bls12_381Field>>::export_scrypt_verifier(
+                vk,
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap_err(),
+            "`alpha` is not a point of the bls12_381 curve"
+        );
+
+        // G2 components in the wrong order
+        let mut vk = self::vk();
+        vk.gamma = vk.gamma.with_order(G2Order::Standard);
+        assert_eq!(
+            vk.check_curve_points(CurveParameter::Bn128).unwrap_err(),
+            "`gamma` is not a point of the bn128 curve"
+        );
+
+        // on the curve, but outside of the prime order subgroup
+        let mut vk = bls12_381_vk();
+        vk.gamma_abc[1] = G1Affine(
+            "0x4".to_string(),
+            "0xa989badd40d6212b33cffc3f3763e9bc760f988c9926b26da9dd85e928483446346b8ed00e1de5d5ea93e354abe706c".to_string(),
+        );
+        assert_eq!(
+            vk.check_curve_points(CurveParameter::Bls12_381).unwrap_err(),
+            "`gamma_abc[1]` is not in the prime order subgroup of the bls12_381 curve"
+        );
+    }
+}
@@ -1,9 +1,17 @@
 use crate::scheme::{Scheme, UniversalScheme};
-use crate::solidity::{solidity_pairing_lib, SolidityCompatibleField, SolidityCompatibleScheme};
+use crate::solidity::{
+    solidity_pairing_lib, SolidityCompatibleField, SolidityCompatibleScheme, SolidityExportConfig,
+};
 use crate::{Fr, G1Affine, G2Affine};
 /* =============== add by sCrypt */
-use crate::scrypt::{scrypt_pairing_lib_bn128};
-use crate::{ScryptCompatibleField, ScryptCompatibleScheme};
+use crate::scheme::groth16::{check_scrypt_constants, BN128_P, BN128_R};
+use crate::scrypt::{scrypt_pair_check_bn128, scrypt_pairing_lib_bn128};
+use crate::{fq_to_biguint, ProofEncoding, ToScryptString};
+use crate::{
+    ScryptCompatibleField, ScryptCompatibleScheme, ScryptExportConfig, ScryptVerifierSections,
+};
+use num_bigint::BigUint;
+use std::borrow::Cow;
 /* =============== end */
 use serde::{Deserialize, Serialize};
 use zokrates_common::helpers::CurveParameter;
@@ -95,11 +103,14 @@ impl<T: Field> UniversalScheme<T> for Marlin {}
 impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for Marlin {
     type Proof = SolidityProof<Fr, G1Affine>;
 
-    fn export_solidity_verifier(vk: <Marlin as Scheme<T>>::VerificationKey) -> String {
+    fn export_solidity_verifier(
+        vk: <Marlin as Scheme<T>>::VerificationKey,
+        config: &SolidityExportConfig,
+    ) -> Result<String, String> {
         use std::fmt::Write;
 
         let (template, solidity_pairing_lib) =
-            (String::from(CONTRACT_TEMPLATE), solidity_pairing_lib(false));
+            (String::from(CONTRACT_TEMPLATE), solidity_pairing_lib(false, &config.precompile));
 
         // Replace public parameters in template
         let src = template
@@ -245,7 +256,7 @@ impl<T: SolidityCompatibleField> SolidityCompatibleScheme<T> for Marlin {
             )
             .replace("<%f_inv%>", "0xc2e1f593efffffff");
 
-        format!("{}{}", solidity_pairing_lib, src)
+        Ok(format!("{}{}", solidity_pairing_lib, src))
     }
 }
 
@@ -700,15 +711,556 @@ contract Verifier {
 impl<T: ScryptCompatibleField> ScryptCompatibleScheme<T> for Marlin {
     type Proof = SolidityProof<Fr, G1Affine>;
 
-    fn export_scrypt_verifier(_vk: <Marlin as Scheme<T>>::VerificationKey, curve_paramter: CurveParameter) -> String {
-        
-        let scrypt_pairing_lib = scrypt_pairing_lib_bn128();
+    /// Exports the AHP and KZG checks of the Marlin verifier on bn128, as the Solidity verifier
+    /// performs them. The Fiat-Shamir transcript is hashed with Keccak-256, which sCrypt does
+    /// not provide, so the challenges are not recomputed on-chain: the contract takes them as
+    /// an argument and is only as sound as the party deriving them from the proof and the
+    /// public inputs. The challenge batching the two KZG openings is derived on-chain.
+    /// The public inputs are arranged as in the Solidity verifier: the constant one, then the
+    /// inputs, padded with zeros to the size of the input domain.
+    fn export_scrypt_verifier_sections(
+        vk: <Marlin as Scheme<T>>::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+    ) -> Result<ScryptVerifierSections, String> {
+        config.check_contract_g2_order()?;
+        if config.domain_tag.is_some() {
+            return Err("Domain tags are only supported for g16".to_string());
+        }
+        if config.subgroup_checks || config.packed_gamma_abc {
+            return Err(
+                "Subgroup checks and packed gamma_abc are only supported for g16".to_string(),
+            );
+        }
+        if config.proof_encoding != ProofEncoding::Scrypt {
+            return Err("Proof encodings other than scrypt are only supported for g16".to_string());
+        }
+        if curve_parameter != CurveParameter::Bn128 {
+            return Err(format!(
+                "Could not export sCrypt verifier for curve {}: not supported",
+                curve_parameter
+            ));
+        }
+        config.check_public_inputs(vk.num_public_inputs)?;
+
+        // the inner sumcheck combines the commitments to row, col, a, b and c val and row_col
+        if vk.index_comms.len() < 6 {
+            return Err(format!(
+                "Expected at least 6 index commitments, found {}",
+                vk.index_comms.len()
+            ));
+        }
+
+        let h_domain_size = vk.num_constraints.next_power_of_two();
+        let k_domain_size = vk.num_non_zero.next_power_of_two();
+        let x_domain_size = vk.num_instance_variables.next_power_of_two();
+        // the public inputs fill the input domain but for its first point, the constant one
+        if vk.num_public_inputs >= x_domain_size {
+            return Err(format!(
+                "Expected fewer than {} public inputs, found {}",
+                x_domain_size, vk.num_public_inputs
+            ));
+        }
+
+        let shift = |bound: usize| match vk
+            .degree_bounds_and_shift_powers
+            .as_ref()
+            .and_then(|s| s.iter().find(|(b, _)| *b == bound))
+        {
+            Some((_, g)) => g.to_scrypt_string(),
+            None => Err(format!("No shift power for the degree bound {}", bound)),
+        };
+
+        let index_comms = vk
+            .index_comms
+            .iter()
+            .map(|(g, _)| g.to_scrypt_string_compact())
+            .collect::<Result<Vec<_>, _>>()?;
+        let vk_text = format!(
+            "{{\n indexComms: [{}],\n g: {},\n gammaG: {},\n h: {},\n betaH: {},\n g1Shift: {},\n g2Shift: {}\n }}",
+            index_comms.join(","),
+            vk.vk.g.to_scrypt_string()?,
+            vk.vk.gamma_g.to_scrypt_string()?,
+            vk.vk.h.to_scrypt_string()?,
+            vk.vk.beta_h.to_scrypt_string()?,
+            shift(h_domain_size - 2)?,
+            shift(k_domain_size - 2)?
+        );
+
+        let r = BigUint::parse_bytes(BN128_R.as_bytes(), 10).unwrap();
+        let inverse = |n: usize| BigUint::from(n).modpow(&(&r - 2u32), &r).to_string();
+        let log = |n: usize| n.trailing_zeros().to_string();
+
+        let template_text = String::from(SCRYPT_TEMPLATE)
+            .replace("<%vk%>", &vk_text)
+            .replace("<%vk_index_comms_length%>", &index_comms.len().to_string())
+            .replace("<%num_public_inputs%>", &vk.num_public_inputs.to_string())
+            .replace("<%h_domain_log_size%>", &log(h_domain_size))
+            .replace("<%k_domain_log_size%>", &log(k_domain_size))
+            .replace("<%x_domain_log_size%>", &log(x_domain_size))
+            .replace(
+                "<%max_domain_log_size%>",
+                &log(h_domain_size.max(k_domain_size).max(x_domain_size)),
+            )
+            .replace("<%k_domain_size_inv%>", &inverse(k_domain_size))
+            .replace("<%x_domain_size_inv%>", &inverse(x_domain_size))
+            .replace(
+                "<%x_root%>",
+                &fq_to_biguint(&vk.x_root_of_unity)?.to_string(),
+            )
+            .replace(
+                "<%pair_check%>",
+                &scrypt_pair_check_bn128(
+                    "BN256.FQ12One",
+                    &[("w", "vk.betaH"), ("c", "vk.h")],
+                    "valid && ",
+                ),
+            );
+
+        let scrypt_pairing_lib: Cow<str> = match &config.pairing_lib_import {
+            Some(module) => format!(
+                "import {{ FixedArray, SmartContractLib, method, prop }} from 'scrypt-ts'\nimport {{ BN256, BN256Pairing, G1Point, G2Point }} from '{}'\n",
+                module
+            )
+            .into(),
+            None => scrypt_pairing_lib_bn128().into(),
+        };
+
+        // the verification key constants precede the types of the `SNARK` class
+        let split = template_text.find("export type VerifyingKey").unwrap_or(0);
+        let (verifying_key_data, snark_class) = template_text.split_at(split);
+        check_scrypt_constants(
+            verifying_key_data,
+            &BigUint::parse_bytes(BN128_P.as_bytes(), 10).unwrap(),
+        )?;
+
+        Ok(ScryptVerifierSections {
+            pairing_lib: config.resolve_imports(scrypt_pairing_lib),
+            verifying_key_data: config.resolve_imports(verifying_key_data).into_owned(),
+            snark_class: config.resolve_imports(snark_class).into_owned(),
+            ..ScryptVerifierSections::default()
+        })
+    }
+}
+
+const SCRYPT_TEMPLATE: &str = r#"
+import { byteString2Int, hash256, int2ByteString, toByteString } from 'scrypt-ts'
 
-        format!(
-            "{}",
-            scrypt_pairing_lib
+export const VERIFYING_KEY_DATA: VerifyingKey = <%vk%>
+
+export const N_PUB_INPUTS = <%num_public_inputs%>
+
+// Base 2 logarithms of the sizes of the domains H of the constraints, K of the non zero
+// entries and X of the inputs, all powers of two
+const H_DOMAIN_LOG_SIZE = <%h_domain_log_size%>n
+const K_DOMAIN_LOG_SIZE = <%k_domain_log_size%>n
+const X_DOMAIN_LOG_SIZE = <%x_domain_log_size%>n
+const MAX_DOMAIN_LOG_SIZE = <%max_domain_log_size%>
+
+const K_DOMAIN_SIZE_INV = <%k_domain_size_inv%>n
+const X_DOMAIN_SIZE_INV = <%x_domain_size_inv%>n
+// Generator of the domain X
+const X_ROOT = <%x_root%>n
+
+export type VerifyingKey = {
+    indexComms: FixedArray<G1Point, <%vk_index_comms_length%>> // row, col, a, b and c val, row_col
+    g: G1Point // Generator of G1
+    gammaG: G1Point // Hiding generator of G1
+    h: G2Point // Generator of G2
+    betaH: G2Point // beta * h
+    g1Shift: G1Point // Shift power of the degree bound of the second round
+    g2Shift: G1Point // Shift power of the degree bound of the third round
+}
+
+export type Proof = {
+    comms1: FixedArray<G1Point, 4> // w, z_a, z_b, mask_poly
+    comms2: FixedArray<G1Point, 3> // t, g_1, h_1
+    degreeBoundComms2G1: G1Point
+    comms3: FixedArray<G1Point, 2> // g_2, h_2
+    degreeBoundComms3G2: G1Point
+    evals: FixedArray<bigint, 4> // g_1, g_2, t and z_b at their query points
+    batchLcProof1: G1Point
+    batchLcProof1R: bigint
+    batchLcProof2: G1Point
+}
+
+// Fiat-Shamir challenges of the proof, as the Solidity verifier samples them out of the
+// Keccak-256 transcript of the key, the public inputs and the proof. They are not
+// recomputed on-chain: the verifier is only as sound as the party deriving them.
+export type Challenges = {
+    alpha: bigint
+    etaA: bigint
+    etaB: bigint
+    etaC: bigint
+    beta: bigint
+    gamma: bigint
+    opening: bigint // Combines the commitments opened at the same point
+}
+
+export class SNARK extends SmartContractLib {
+    @prop()
+    static readonly N: bigint = BigInt(N_PUB_INPUTS) // Number of public inputs.
+
+    @method()
+    static addFr(a: bigint, b: bigint): bigint {
+        return BN256.modReduce(a + b, BN256.R)
+    }
+
+    @method()
+    static subFr(a: bigint, b: bigint): bigint {
+        return BN256.modReduce(a - b, BN256.R)
+    }
+
+    @method()
+    static mulFr(a: bigint, b: bigint): bigint {
+        return BN256.modReduce(a * b, BN256.R)
+    }
+
+    @method()
+    static inverseFr(a: bigint): bigint {
+        return BN256.modInverseEGCD(a, BN256.R)
+    }
+
+    @method()
+    static negG1Point(a: G1Point): G1Point {
+        return {
+            x: a.x,
+            y: BN256.modReduce(-a.y, BN256.P),
+        }
+    }
+
+    // x^n - 1 for the size n = 2^logSize of a domain
+    @method()
+    static evalVanishingPoly(x: bigint, logSize: bigint): bigint {
+        let res = x
+        for (let i = 0; i < MAX_DOMAIN_LOG_SIZE; i++) {
+            if (BigInt(i) < logSize) {
+                res = SNARK.mulFr(res, res)
+            }
+        }
+        return SNARK.subFr(res, 1n)
+    }
+
+    // Lagrange basis polynomial of the domain X at the point omega, evaluated at x, given
+    // v_X(x) / |X|
+    @method()
+    static lagrangeCoeff(x: bigint, omega: bigint, vXOverSize: bigint): bigint {
+        let res = 0n
+        if (vXOverSize == 0n) {
+            if (x == omega) {
+                res = 1n
+            }
+        } else {
+            res = SNARK.mulFr(
+                SNARK.mulFr(vXOverSize, omega),
+                SNARK.inverseFr(SNARK.subFr(x, omega))
+            )
+        }
+        return res
+    }
+
+    // Polynomial of the domain X interpolating the constant one followed by the public
+    // inputs, padded with zeros, evaluated at x
+    @method()
+    static evalInputPoly(
+        x: bigint,
+        inputs: FixedArray<bigint, typeof N_PUB_INPUTS>
+    ): bigint {
+        const vXOverSize = SNARK.mulFr(
+            SNARK.evalVanishingPoly(x, X_DOMAIN_LOG_SIZE),
+            X_DOMAIN_SIZE_INV
+        )
+        let res = SNARK.lagrangeCoeff(x, 1n, vXOverSize)
+        let omega = 1n
+        for (let i = 0; i < N_PUB_INPUTS; i++) {
+            omega = SNARK.mulFr(omega, X_ROOT)
+            res = SNARK.addFr(
+                res,
+                SNARK.mulFr(SNARK.lagrangeCoeff(x, omega, vXOverSize), inputs[i])
+            )
+        }
+        return res
+    }
+
+    // Challenge combining the KZG openings at beta and gamma. Valid openings pass the
+    // check for any value, so it is derived from them on-chain rather than taken as an
+    // argument.
+    @method()
+    static batchingChallenge(c0: G1Point, c1: G1Point, proof: Proof): bigint {
+        const digest = hash256(
+            int2ByteString(c0.x, 32n) +
+                int2ByteString(c0.y, 32n) +
+                int2ByteString(c1.x, 32n) +
+                int2ByteString(c1.y, 32n) +
+                int2ByteString(proof.batchLcProof1.x, 32n) +
+                int2ByteString(proof.batchLcProof1.y, 32n) +
+                int2ByteString(proof.batchLcProof2.x, 32n) +
+                int2ByteString(proof.batchLcProof2.y, 32n)
+        )
+        // a zero sign byte reads the digest as an unsigned integer
+        return BN256.modReduce(
+            byteString2Int(digest + toByteString('00')),
+            BN256.R
+        )
+    }
+
+    @method()
+    static verify(
+        vk: VerifyingKey,
+        inputs: FixedArray<bigint, typeof N_PUB_INPUTS>,
+        proof: Proof,
+        challenges: Challenges,
+    ): boolean {
+        const alpha = challenges.alpha
+        const beta = challenges.beta
+        const gamma = challenges.gamma
+        const xi = challenges.opening
+
+        let valid = alpha < BN256.R && beta < BN256.R && gamma < BN256.R && xi < BN256.R
+        for (let i = 0; i < N_PUB_INPUTS; i++) {
+            valid = valid && inputs[i] >= 0n && inputs[i] < BN256.R
+        }
+
+        const vHAlpha = SNARK.evalVanishingPoly(alpha, H_DOMAIN_LOG_SIZE)
+        const vHBeta = SNARK.evalVanishingPoly(beta, H_DOMAIN_LOG_SIZE)
+        // alpha and beta are sampled outside of H
+        valid = valid && vHAlpha != 0n && vHBeta != 0n && alpha != beta
+
+        // unnormalized bivariate Lagrange polynomial of H at (alpha, beta)
+        const rHAlphaBeta = SNARK.mulFr(
+            SNARK.subFr(vHAlpha, vHBeta),
+            SNARK.inverseFr(SNARK.subFr(alpha, beta))
+        )
+        const vXBeta = SNARK.evalVanishingPoly(beta, X_DOMAIN_LOG_SIZE)
+        const xBeta = SNARK.evalInputPoly(beta, inputs)
+        const vKGamma = SNARK.evalVanishingPoly(gamma, K_DOMAIN_LOG_SIZE)
+
+        // outer sumcheck: mask_poly, z_a, 1, w, 1, h_1, 1
+        const outerZA = SNARK.mulFr(
+            rHAlphaBeta,
+            SNARK.addFr(challenges.etaA, SNARK.mulFr(challenges.etaC, proof.evals[3]))
+        )
+        const outerOne = SNARK.addFr(
+            SNARK.addFr(
+                SNARK.mulFr(rHAlphaBeta, SNARK.mulFr(challenges.etaB, proof.evals[3])),
+                SNARK.mulFr(xBeta, SNARK.subFr(0n, proof.evals[2]))
+            ),
+            SNARK.mulFr(proof.evals[0], SNARK.subFr(0n, beta))
+        )
+        let outerComm = BN256.addG1Points(
+            proof.comms1[3],
+            BN256.mulG1Point(proof.comms1[1], outerZA)
+        )
+        outerComm = BN256.addG1Points(
+            outerComm,
+            BN256.mulG1Point(
+                proof.comms1[0],
+                SNARK.mulFr(vXBeta, SNARK.subFr(0n, proof.evals[2]))
+            )
+        )
+        outerComm = BN256.addG1Points(
+            outerComm,
+            BN256.mulG1Point(proof.comms2[2], SNARK.subFr(0n, vHBeta))
+        )
+        const outerEval = SNARK.subFr(0n, outerOne)
+
+        // openings at beta: g_1 with its degree bound, outer_sc, t, z_b
+        let xiPow = xi
+        let comm0 = BN256.addG1Points(
+            proof.comms2[1],
+            BN256.mulG1Point(
+                BN256.addG1Points(
+                    proof.degreeBoundComms2G1,
+                    SNARK.negG1Point(BN256.mulG1Point(vk.g1Shift, proof.evals[0]))
+                ),
+                xiPow
+            )
+        )
+        let eval0 = proof.evals[0]
+        xiPow = SNARK.mulFr(xiPow, xi)
+        comm0 = BN256.addG1Points(comm0, BN256.mulG1Point(outerComm, xiPow))
+        eval0 = SNARK.addFr(eval0, SNARK.mulFr(outerEval, xiPow))
+        xiPow = SNARK.mulFr(xiPow, xi)
+        comm0 = BN256.addG1Points(comm0, BN256.mulG1Point(proof.comms2[0], xiPow))
+        eval0 = SNARK.addFr(eval0, SNARK.mulFr(proof.evals[2], xiPow))
+        xiPow = SNARK.mulFr(xiPow, xi)
+        comm0 = BN256.addG1Points(comm0, BN256.mulG1Point(proof.comms1[2], xiPow))
+        eval0 = SNARK.addFr(eval0, SNARK.mulFr(proof.evals[3], xiPow))
+
+        // inner sumcheck: a, b and c val, 1, row, col, row_col, h_2
+        const aCoeff = SNARK.mulFr(vHAlpha, vHBeta)
+        const bCoeff = SNARK.addFr(
+            SNARK.mulFr(gamma, proof.evals[1]),
+            SNARK.mulFr(proof.evals[2], K_DOMAIN_SIZE_INV)
+        )
+        let innerComm = BN256.mulG1Point(
+            vk.indexComms[2],
+            SNARK.mulFr(challenges.etaA, aCoeff)
+        )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(vk.indexComms[3], SNARK.mulFr(challenges.etaB, aCoeff))
+        )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(vk.indexComms[4], SNARK.mulFr(challenges.etaC, aCoeff))
         )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(vk.indexComms[0], SNARK.mulFr(bCoeff, alpha))
+        )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(vk.indexComms[1], SNARK.mulFr(bCoeff, beta))
+        )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(vk.indexComms[5], SNARK.subFr(0n, bCoeff))
+        )
+        innerComm = BN256.addG1Points(
+            innerComm,
+            BN256.mulG1Point(proof.comms3[1], SNARK.subFr(0n, vKGamma))
+        )
+        const innerEval = SNARK.mulFr(bCoeff, SNARK.mulFr(beta, alpha))
+
+        // openings at gamma: g_2 with its degree bound, inner_sc
+        let comm1 = BN256.addG1Points(
+            proof.comms3[0],
+            BN256.mulG1Point(
+                BN256.addG1Points(
+                    proof.degreeBoundComms3G2,
+                    SNARK.negG1Point(BN256.mulG1Point(vk.g2Shift, proof.evals[1]))
+                ),
+                xi
+            )
+        )
+        const xiSquare = SNARK.mulFr(xi, xi)
+        comm1 = BN256.addG1Points(comm1, BN256.mulG1Point(innerComm, xiSquare))
+        const eval1 = SNARK.addFr(proof.evals[1], SNARK.mulFr(innerEval, xiSquare))
+
+        // final pairing check of both openings
+        const r = SNARK.batchingChallenge(comm0, comm1, proof)
+        let c = BN256.addG1Points(comm0, BN256.mulG1Point(proof.batchLcProof1, beta))
+        c = BN256.addG1Points(
+            c,
+            BN256.mulG1Point(
+                BN256.addG1Points(comm1, BN256.mulG1Point(proof.batchLcProof2, gamma)),
+                r
+            )
+        )
+        c = BN256.addG1Points(
+            c,
+            SNARK.negG1Point(BN256.mulG1Point(vk.g, SNARK.addFr(eval0, SNARK.mulFr(eval1, r))))
+        )
+        c = BN256.addG1Points(
+            c,
+            SNARK.negG1Point(BN256.mulG1Point(vk.gammaG, proof.batchLcProof1R))
+        )
+        const w = SNARK.negG1Point(
+            BN256.addG1Points(proof.batchLcProof1, BN256.mulG1Point(proof.batchLcProof2, r))
+        )
+        <%pair_check%>
     }
 }
 
-/* =============== end */
\ No newline at end of file
+"#;
+
+/* =============== end */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zokrates_field::Bn128Field;
+
+    fn vk() -> VerificationKey<Fr, G1Affine, G2Affine> {
+        let g1 = G1Affine("0x1".to_string(), "0x2".to_string());
+        let g2 = G2Affine::Fq2(crate::G2AffineFq2(
+            ("0x1".to_string(), "0x2".to_string()),
+            ("0x3".to_string(), "0x4".to_string()),
+        ));
+        VerificationKey {
+            fs_seed: vec![],
+            x_root_of_unity: "0x1".to_string(),
+            num_public_inputs: 2,
+            num_variables: 4,
+            num_constraints: 4,
+            num_non_zero: 4,
+            num_instance_variables: 3,
+            index_comms: vec![(g1.clone(), None); 6],
+            vk: KZGVerifierKey {
+                g: g1.clone(),
+                gamma_g: g1.clone(),
+                h: g2.clone(),
+                beta_h: g2,
+            },
+            max_degree: 8,
+            supported_degree: 8,
+            degree_bounds_and_shift_powers: Some(vec![(2, g1)]),
+        }
+    }
+
+    #[test]
+    fn export_scrypt_verifier() {
+        let sections =
+            <Marlin as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier_sections(
+                vk(),
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .unwrap();
+        assert!(sections
+            .verifying_key_data
+            .contains("export const VERIFYING_KEY_DATA: VerifyingKey = {\n indexComms: [{"));
+        assert!(sections
+            .verifying_key_data
+            .contains("export const N_PUB_INPUTS = 2"));
+        assert!(sections
+            .verifying_key_data
+            .contains("const X_DOMAIN_LOG_SIZE = 2n"));
+        assert!(sections
+            .snark_class
+            .contains("indexComms: FixedArray<G1Point, 6>"));
+        assert!(sections
+            .snark_class
+            .contains("return valid && BN256Pairing.pairCheckFinal(acc)"));
+        assert!(!sections.join().contains("<%"));
+
+        // the emitted inverse of the size of K is exact
+        let r = BigUint::parse_bytes(BN128_R.as_bytes(), 10).unwrap();
+        let inv = BigUint::parse_bytes(
+            sections
+                .verifying_key_data
+                .split("const K_DOMAIN_SIZE_INV = ")
+                .nth(1)
+                .unwrap()
+                .split('n')
+                .next()
+                .unwrap()
+                .as_bytes(),
+            10,
+        )
+        .unwrap();
+        assert_eq!(inv * 4u32 % r, BigUint::from(1u32));
+
+        assert!(
+            <Marlin as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                vk(),
+                CurveParameter::Bls12_381,
+                &ScryptExportConfig::default(),
+            )
+            .is_err()
+        );
+
+        // the inner sumcheck needs all index commitments
+        let mut short = vk();
+        short.index_comms.truncate(5);
+        assert!(
+            <Marlin as ScryptCompatibleScheme<Bn128Field>>::export_scrypt_verifier(
+                short,
+                CurveParameter::Bn128,
+                &ScryptExportConfig::default(),
+            )
+            .is_err()
+        );
+    }
+}
@@ -1,19 +1,389 @@
-use crate::Scheme;
+use crate::{G2Order, ProofEncoding, Scheme};
+use lazy_static::lazy_static;
+use num_bigint::BigUint;
+use num_traits::{One, Zero};
+use regex::Regex;
 use serde::{de::DeserializeOwned, Serialize};
+use std::borrow::Cow;
+use std::io::Write;
 use zokrates_common::helpers::CurveParameter;
-use zokrates_field::{Bn128Field, Bls12_381Field, Field};
+use zokrates_field::{Bn128Field, Bls12_381Field, Bw6_761Field, Field};
 
 pub trait ScryptCompatibleField: Field {}
 impl ScryptCompatibleField for Bn128Field {}
 impl ScryptCompatibleField for Bls12_381Field {}
+impl ScryptCompatibleField for Bw6_761Field {}
 pub trait ScryptCompatibleScheme<T: ScryptCompatibleField>: Scheme<T> {
     type Proof: From<Self::ProofPoints> + Serialize + DeserializeOwned + Clone;
 
-    fn export_scrypt_verifier(vk: Self::VerificationKey, curve_parameter: CurveParameter) -> String;
+    fn export_scrypt_verifier_sections(
+        vk: Self::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+    ) -> Result<ScryptVerifierSections, String>;
+
+    fn export_scrypt_verifier(
+        vk: Self::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+    ) -> Result<String, String> {
+        Self::export_scrypt_verifier_sections(vk, curve_parameter, config).map(|s| s.join())
+    }
+
+    /// Writes the verifier to `out` section by section, as `export_scrypt_verifier` returns it
+    fn write_scrypt_verifier<W: Write>(
+        vk: Self::VerificationKey,
+        curve_parameter: CurveParameter,
+        config: &ScryptExportConfig,
+        out: &mut W,
+    ) -> Result<(), String> {
+        Self::export_scrypt_verifier_sections(vk, curve_parameter, config)?
+            .write_to(out)
+            .map_err(|why| format!("Could not write verifier: {}", why))
+    }
+
+    /// Exports the `VERIFYING_KEY_DATA` object literal of the verifier alone, completed with
+    /// `millerb1a1`, the miller loop of alpha and beta as computed by a backend
+    fn export_verifying_key_json(
+        _vk: Self::VerificationKey,
+        _curve_parameter: CurveParameter,
+        _config: &ScryptExportConfig,
+        _miller_beta_alpha: &str,
+    ) -> Result<String, String> {
+        Err("Exporting the verifying key alone is not supported for this scheme".to_string())
+    }
+}
+
+/// Language an exported verifier is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScryptLanguage {
+    /// scrypt-ts, compiled within the TypeScript project scaffolding
+    ScryptTs,
+    /// The sCrypt language, compiled on its own as a `.scrypt` contract
+    Scrypt,
+}
+
+impl Default for ScryptLanguage {
+    fn default() -> Self {
+        ScryptLanguage::ScryptTs
+    }
+}
+
+impl ScryptLanguage {
+    /// Extension of the files holding verifiers written in this language
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            ScryptLanguage::ScryptTs => "ts",
+            ScryptLanguage::Scrypt => "scrypt",
+        }
+    }
+}
+
+/// An exported sCrypt verifier, split into the pairing library, the verification key
+/// constants and the `SNARK` class with its types. An embedded pairing library is
+/// borrowed rather than copied into each verifier.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ScryptVerifierSections {
+    pub pairing_lib: Cow<'static, str>,
+    pub verifying_key_data: String,
+    pub snark_class: String,
+    pub language: ScryptLanguage,
+}
+
+impl ScryptVerifierSections {
+    /// Concatenates the sections with LF line endings and a single trailing newline
+    pub fn join(&self) -> String {
+        let mut out = vec![];
+        // writing to a vector does not fail
+        self.write_to(&mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Writes the concatenation of the sections with LF line endings and a single trailing
+    /// newline to `out`, one section at a time and without copying them
+    pub fn write_to<W: Write>(&self, out: &mut W) -> std::io::Result<()> {
+        // newlines at the end of the sections written so far, only written once followed by text
+        let mut pending_newlines = 0;
+        // a CR ending a section forms a single line ending with a LF starting the next one
+        let mut after_cr = false;
+
+        for section in [
+            &*self.pairing_lib,
+            self.verifying_key_data.as_str(),
+            self.snark_class.as_str(),
+        ] {
+            let section = match after_cr {
+                true => section.strip_prefix('\n').unwrap_or(section),
+                false => section,
+            };
+            if !section.is_empty() {
+                after_cr = section.ends_with('\r');
+            }
+
+            let body = section.trim_end_matches(|c| c == '\n' || c == '\r');
+            if !body.is_empty() {
+                out.write_all("\n".repeat(pending_newlines).as_bytes())?;
+                // a CR, alone or followed by a LF, ends a line
+                for (i, line) in body.split('\r').enumerate() {
+                    if i > 0 && !line.starts_with('\n') {
+                        out.write_all(b"\n")?;
+                    }
+                    out.write_all(line.as_bytes())?;
+                }
+                pending_newlines = 0;
+            }
+            let trailing = &section[body.len()..];
+            pending_newlines += trailing.matches('\n').count() + trailing.matches('\r').count()
+                - trailing.matches("\r\n").count();
+        }
+
+        out.write_all(b"\n")
+    }
+}
+
+/// Options of the sCrypt verifier export
+#[derive(Debug, Clone, Default)]
+pub struct ScryptExportConfig {
+    pub g2_order: G2Order,
+    /// Check on-chain that the proof points lie in the prime order subgroups
+    pub subgroup_checks: bool,
+    /// Module specifier of a shared pairing library to import instead of inlining it
+    pub pairing_lib_import: Option<String>,
+    /// Module specifier `scrypt-ts` is imported from, `'scrypt-ts'` if not set
+    pub scrypt_ts_import: Option<String>,
+    /// Store `gammaAbc` as x coordinates and y parities, unpacked on-chain
+    pub packed_gamma_abc: bool,
+    /// Hex value the first public input is fixed to, binding the verifier to a domain
+    pub domain_tag: Option<String>,
+    pub proof_encoding: ProofEncoding,
+    /// Most public inputs a verifier may take, `MAX_SCRYPT_PUBLIC_INPUTS` if not set
+    pub max_public_inputs: Option<usize>,
+}
+
+impl ScryptExportConfig {
+    pub fn g2_order(mut self, order: G2Order) -> Self {
+        self.g2_order = order;
+        self
+    }
+
+    pub fn subgroup_checks(mut self, flag: bool) -> Self {
+        self.subgroup_checks = flag;
+        self
+    }
+
+    pub fn pairing_lib_import<S: Into<String>>(mut self, module: S) -> Self {
+        self.pairing_lib_import = Some(module.into());
+        self
+    }
+
+    pub fn scrypt_ts_import<S: Into<String>>(mut self, module: S) -> Self {
+        self.scrypt_ts_import = Some(module.into());
+        self
+    }
+
+    pub fn packed_gamma_abc(mut self, flag: bool) -> Self {
+        self.packed_gamma_abc = flag;
+        self
+    }
+
+    pub fn domain_tag<S: Into<String>>(mut self, tag: S) -> Self {
+        self.domain_tag = Some(tag.into());
+        self
+    }
+
+    pub fn proof_encoding(mut self, encoding: ProofEncoding) -> Self {
+        self.proof_encoding = encoding;
+        self
+    }
+
+    pub fn max_public_inputs(mut self, max: usize) -> Self {
+        self.max_public_inputs = Some(max);
+        self
+    }
+
+    /// Fails unless G2 points are emitted in the `scrypt` order, the only one a verifier
+    /// contract accepts as its pairing library swaps them back with `createTwistPoint`
+    pub(crate) fn check_contract_g2_order(&self) -> Result<(), String> {
+        match self.g2_order {
+            G2Order::Scrypt => Ok(()),
+            G2Order::Standard => Err(
+                "The standard G2 order is only supported for verifying key exports, a verifier contract swaps G2 components with `createTwistPoint`"
+                    .to_string(),
+            ),
+        }
+    }
+
+    /// Fails if a verifier of `count` public inputs exceeds the configured limit
+    pub(crate) fn check_public_inputs(&self, count: usize) -> Result<(), String> {
+        let max = self.max_public_inputs.unwrap_or(MAX_SCRYPT_PUBLIC_INPUTS);
+        if count > max {
+            return Err(format!(
+                "The verifier takes {} public inputs, more than the limit of {}: its script would likely exceed the sCrypt size constraints. Consider aggregating the inputs, e.g. by hashing them into a single public input",
+                count, max
+            ));
+        }
+        Ok(())
+    }
+
+    /// Points the `scrypt-ts` imports of `text` at the configured module specifier
+    pub(crate) fn resolve_imports<'a>(&self, text: impl Into<Cow<'a, str>>) -> Cow<'a, str> {
+        let text = text.into();
+        match &self.scrypt_ts_import {
+            Some(module) => text
+                .replace("from 'scrypt-ts'", &format!("from '{}'", module))
+                .into(),
+            None => text,
+        }
+    }
+}
+
+/// Default limit of the number of public inputs of an exported verifier. The input loop
+/// is unrolled, so the script grows by a scalar multiplication per input.
+pub const MAX_SCRYPT_PUBLIC_INPUTS: usize = 64;
+
+/// Order of the secp256k1 group, the modulus of the Pedersen key-proof scalars
+pub const SECP256K1_ORDER: &str =
+    "115792089237316195423570985008687907852837564279074904382605163141518161494337";
+
+/// Upper bound of the eGCD mod inverse loop modulo `SECP256K1_ORDER`,
+/// see `egcd_iteration_bound`
+pub const SECP256K1_UB: usize = 368;
+
+/// Worst-case number of division steps taken by the eGCD mod inverse loop
+/// (`modInverseEGCD`) for an input reduced modulo `modulus`.
+///
+/// By Lamé's theorem, if Euclid's algorithm on `(m, x)` with `x < m` takes `n` steps,
+/// then `m >= F(n + 2)` where `F` is the Fibonacci sequence. The bound is therefore
+/// `k - 2` where `F(k)` is the largest Fibonacci number not exceeding `modulus`.
+/// This yields 364 for the BN256 prime (`UB = 368`), 548 for the BLS12-381 prime
+/// (`UB = 548`) and 368 for the secp256k1 order.
+pub fn egcd_iteration_bound(modulus: &BigUint) -> usize {
+    let (mut previous, mut current) = (BigUint::zero(), BigUint::one());
+    let mut k = 1;
+    while &(&previous + &current) <= modulus {
+        let next = &previous + &current;
+        previous = current;
+        current = next;
+        k += 1;
+    }
+    k.saturating_sub(2)
+}
+
+/// Absolute value of the BLS12-381 curve parameter `x` (`x` is negative)
+pub const BLS12_381_X: u64 = 0xd201000000010000;
+
+/// Steps of the Miller loop over the bits of `x` below the most significant one: a
+/// doubling step (`D`) per bit, followed by an addition step (`A`) for each set bit.
+/// The BLS12-381 `preCompute` and `millerLoop` are unrolled along this sequence.
+pub fn miller_loop_steps(x: u64) -> String {
+    let bits = 63 - x.leading_zeros();
+    (0..bits)
+        .rev()
+        .map(|i| if x >> i & 1 == 1 { "DA" } else { "D" })
+        .collect()
 }
 
+/// Formats the 12 decimal limbs of an Fq12 element, listed in the order the backends
+/// print them (`c0.c0.c0` first), as the sCrypt `FQ12` literal of `millerb1a1`.
+/// Shared by all backends so that their exported verifiers are interchangeable.
+pub fn fq12_to_scrypt_string(limbs: &[String]) -> String {
+    assert_eq!(limbs.len(), 12, "an Fq12 element has 12 limbs");
+    format!(
+        r#"{{
+                x: {{
+                    x: {{
+                        x: {}n,
+                        y: {}n
+                    }},
+                    y: {{
+                        x: {}n,
+                        y: {}n
+                    }},
+                    z: {{
+                        x: {}n,
+                        y: {}n
+                    }}
+                }},
+                y: {{
+                    x: {{
+                        x: {}n,
+                        y: {}n
+                    }},
+                    y: {{
+                        x: {}n,
+                        y: {}n
+                    }},
+                    z: {{
+                        x: {}n,
+                        y: {}n
+                    }}
+                }}
+            }}"#,
+        limbs[11],
+        limbs[10],
+        limbs[9],
+        limbs[8],
+        limbs[7],
+        limbs[6],
+        limbs[5],
+        limbs[4],
+        limbs[3],
+        limbs[2],
+        limbs[1],
+        limbs[0]
+    )
+}
+
+/// `return` statement of a BN128 verifier checking that the product of the pairings of
+/// `pairs` and of the precomputed miller loop `precalc` is one, with `condition` prefixed to
+/// the check. Three pairs fit `pairCheckP4Precalc`, any other number is accumulated with
+/// `millerAccumulate` before a single final exponentiation.
+pub fn scrypt_pair_check_bn128(precalc: &str, pairs: &[(&str, &str)], condition: &str) -> String {
+    if let [(a0, b0), (a1, b1), (a2, b2)] = pairs {
+        return format!(
+            "return {}BN256Pairing.pairCheckP4Precalc(
+            {},
+            {},
+            {},
+            {},
+            {},
+            {},
+            {}
+        )",
+            condition, a0, b0, precalc, a1, b1, a2, b2
+        );
+    }
+
+    let mut statement = format!("let acc = {}\n", precalc);
+    for (a, b) in pairs {
+        statement.push_str(&format!(
+            "        acc = BN256Pairing.millerAccumulate(acc, {}, {})\n",
+            a, b
+        ));
+    }
+    statement.push_str(&format!(
+        "        return {}BN256Pairing.pairCheckFinal(acc)",
+        condition
+    ));
+    statement
+}
 
-pub fn scrypt_pairing_lib_bn128() -> String {
+lazy_static! {
+    static ref SCRYPT_PAIRING_LIB_BN128: String = build_scrypt_pairing_lib_bn128();
+    static ref SCRYPT_PAIRING_LIB_BLS12_381: String = build_scrypt_pairing_lib_bls12_381();
+}
+
+/// The `BN256` and `BN256Pairing` libraries, built once per process
+pub fn scrypt_pairing_lib_bn128() -> &'static str {
+    &SCRYPT_PAIRING_LIB_BN128
+}
+
+/// The `BLS12381` and `BLS12381Pairing` libraries, built once per process
+pub fn scrypt_pairing_lib_bls12_381() -> &'static str {
+    &SCRYPT_PAIRING_LIB_BLS12_381
+}
+
+fn build_scrypt_pairing_lib_bn128() -> String {
     let bn256_lib = r#"import { and, SmartContractLib, method, lshift, prop, FixedArray } from 'scrypt-ts'
 
 export type FQ = bigint
@@ -63,6 +433,12 @@ export type G2Point = {
     y: FQ2
 }
 
+// A G1Point stored as its x coordinate and the parity of its y coordinate.
+export type PackedG1Point = {
+    x: FQ
+    yOdd: boolean
+}
+
 export class BN256 extends SmartContractLib {
     // Curve bits:
     @prop()
@@ -153,6 +529,16 @@ export class BN256 extends SmartContractLib {
     static readonly P: bigint =
         21888242871839275222246405745257275088696311157297823662689037894645226208583n
 
+    // Order of the G1 and G2 subgroups:
+    @prop()
+    static readonly R: bigint =
+        21888242871839275222246405745257275088548364400416034343698204186575808495617n
+
+    // (P + 1) / 4, as P = 3 mod 4 a^((P + 1) / 4) is a square root of a if a is a square:
+    @prop()
+    static readonly P_PLUS_1_DIV_4: bigint =
+        5472060717959818805561601436314318772174077789324455915672259473661306552146n
+
     // xiToPMinus1Over6 is ξ^((p-1)/6) where ξ = i+9.
     @prop()
     static readonly xiToPMinus1Over6: FQ2 = {
@@ -1122,6 +1508,57 @@ export class BN256 extends SmartContractLib {
         return res
     }
 
+    @method()
+    static mulTwistPoint(a: TwistPoint, m: bigint): TwistPoint {
+        let res: TwistPoint = {
+            x: BN256.FQ2Zero,
+            y: BN256.FQ2One,
+            z: BN256.FQ2Zero,
+            t: BN256.FQ2Zero,
+        }
+
+        if (m != 0n) {
+            // Double and add method.
+            // Lowest bit to highest.
+            let t: TwistPoint = {
+                x: BN256.FQ2Zero,
+                y: BN256.FQ2Zero,
+                z: BN256.FQ2Zero,
+                t: BN256.FQ2Zero,
+            }
+            let sum: TwistPoint = {
+                x: BN256.FQ2Zero,
+                y: BN256.FQ2Zero,
+                z: BN256.FQ2Zero,
+                t: BN256.FQ2Zero,
+            }
+
+            let firstOne = false
+
+            for (let k = 0; k < BN256.CURVE_BITS_P8_DIV12; k++) {
+                sum = BN256.modTwistPoint(sum)
+                for (let j = 0; j < 3; j++) {
+                    if (firstOne) {
+                        t = BN256.doubleTwistPoint(sum)
+                    }
+                    const shifted = lshift(
+                        1n,
+                        BigInt(Number(BN256.CURVE_BITS_P8) - 1 - (3 * k + j))
+                    )
+                    if (and(m, shifted) != 0n) {
+                        firstOne = true
+                        sum = BN256.addTwistPoints(t, a)
+                    } else {
+                        sum = t
+                    }
+                }
+            }
+            res = sum
+        }
+
+        return res
+    }
+
     @method()
     static makeAffineCurvePoint(a: CurvePoint): CurvePoint {
         // MakeAffine converts a to affine form. If c is ∞, then it sets
@@ -1190,6 +1627,36 @@ export class BN256 extends SmartContractLib {
         return res
     }
 
+    @method()
+    static sqrtFQ(a: FQ): FQ {
+        // Square and multiply, highest bit to lowest.
+        let res = 1n
+        for (let i = 0; i < BN256.CURVE_BITS; i++) {
+            res = BN256.modReduce(res * res, BN256.P)
+            const shifted = lshift(
+                1n,
+                BigInt(Number(BN256.CURVE_BITS) - 1 - i)
+            )
+            if (and(BN256.P_PLUS_1_DIV_4, shifted) != 0n) {
+                res = BN256.modReduce(res * a, BN256.P)
+            }
+        }
+        return res
+    }
+
+    @method()
+    static unpackG1Point(p: PackedG1Point): G1Point {
+        // y² = x³ + 3
+        let y = BN256.sqrtFQ(BN256.modReduce(p.x * p.x * p.x + 3n, BN256.P))
+        if ((BN256.modReduce(y, 2n) == 1n) != p.yOdd) {
+            y = BN256.P - y
+        }
+        return {
+            x: p.x,
+            y: y,
+        }
+    }
+
     @method()
     static getG1Point(cp: CurvePoint): G1Point {
         const acp = BN256.makeAffineCurvePoint(cp)
@@ -2405,18 +2872,119 @@ export class BN256Pairing extends SmartContractLib {
             BN256.createTwistPoint(b1)
         )
     }
+
+    // Multiplies acc by the miller loop of (a, b). Checks of any number of pairings
+    // accumulate each pair, then call pairCheckFinal once.
+    @method()
+    static millerAccumulate(acc: FQ12, a: G1Point, b: G2Point): FQ12 {
+        const p = BN256.makeAffineCurvePoint(BN256.createCurvePoint(a))
+        const q = BN256.createTwistPoint(b)
+
+        if (!BN256.isInfCurvePoint(p) && !BN256.isInfTwistPoint(q)) {
+            acc = BN256.mulFQ12(acc, BN256Pairing.miller(q, p))
+        }
+
+        return BN256.modFQ12(acc)
+    }
+
+    @method()
+    static pairCheckFinal(acc: FQ12): boolean {
+        return BN256.compareFQ12(
+            BN256Pairing.finalExponentiation(acc),
+            BN256.FQ12One
+        )
+    }
+
+    @method()
+    static millerFused3(<%miller_fused_3_parameters%>): FQ12 {
+<%miller_fused_3%>
+    }
 }
  
 "#;
 
-    [
-        bn256_lib,
-        pairing_lib,
-    ]
-    .join("\n")
+    let miller = miller_body_bn128(pairing_lib);
+    let pairing_lib = pairing_lib
+        .replace(
+            "<%miller_fused_3_parameters%>",
+            &(0..3)
+                .map(|i| format!("q_{0}: TwistPoint, p_{0}: CurvePoint", i))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+        .replace("<%miller_fused_3%>", &fused_miller_bn128(miller, 3));
+
+    [bn256_lib, pairing_lib.as_str()].join("\n")
 }
 
-pub fn scrypt_pairing_lib_bls12_381() -> String {
+/// Body of the unrolled single pair `miller(q, p)` of the `BN256Pairing` library
+fn miller_body_bn128(pairing_lib: &str) -> &str {
+    let signature = "    static miller(q: TwistPoint, p: CurvePoint): FQ12 {\n";
+    let start = pairing_lib.find(signature).unwrap() + signature.len();
+    let end = start + pairing_lib[start..].find("\n    }\n").unwrap();
+    &pairing_lib[start..end]
+}
+
+/// Fuses the Miller loops of `n` pairs `(q_i, p_i)` along their shared `6u+2` schedule: each
+/// step squares the accumulator once, then multiplies in the line functions of every pair.
+/// `miller` is the body of the single pair loop, whose statements not involving the
+/// accumulator `ret` are repeated for each pair, with their variables suffixed by `_i`.
+fn fused_miller_bn128(miller: &str, n: usize) -> String {
+    let variables =
+        Regex::new(r"\b(aAffine|bAffine|minusA|r|r2|q1x|q1y|q1|minusQ2x|minusQ2|lfr|q|p)\b")
+            .unwrap();
+    let accumulator = Regex::new(r"\bret\b").unwrap();
+    let for_each_pair = |statement: &str| {
+        (0..n)
+            .map(|i| {
+                variables
+                    .replace_all(statement, format!("${{1}}_{}", i).as_str())
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // group the lines into statements, some of which span several lines
+    let mut statements = vec![];
+    let mut statement = vec![];
+    let mut depth = 0;
+    for line in miller.lines() {
+        statement.push(line);
+        if !line.trim_start().starts_with("//") {
+            depth += line.matches(|c| c == '(' || c == '{').count();
+            depth -= line.matches(|c| c == ')' || c == '}').count();
+        }
+        if depth == 0 {
+            statements.push(statement.join("\n"));
+            statement.clear();
+        }
+    }
+
+    let last_line = "        return BN256.modFQ12(BN256Pairing.mulLine(ret, lfr.a, lfr.b, lfr.c))";
+    statements
+        .into_iter()
+        .flat_map(|statement| {
+            let trimmed = statement.trim_start();
+            if statement == last_line {
+                let mut last = for_each_pair(
+                    "        ret = BN256Pairing.mulLine(ret, lfr.a, lfr.b, lfr.c)",
+                );
+                last.push("        return BN256.modFQ12(ret)".to_string());
+                last
+            } else if trimmed.is_empty()
+                || trimmed.starts_with("//")
+                || (accumulator.is_match(&statement) && !statement.contains("lfr"))
+            {
+                vec![statement]
+            } else {
+                for_each_pair(&statement)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn build_scrypt_pairing_lib_bls12_381() -> String {
     let bls12_381_lib = r#"
 type fe   = int;
 type fe2  = fe[2];
@@ -4328,4 +4896,209 @@ library BLS12381Pairing {
         pairing_lib,
     ]
     .join("\n")
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_traits::Num;
+    use regex::Regex;
+
+    #[test]
+    fn fq12_limb_order() {
+        let limbs: Vec<String> = (0..12).map(|i| i.to_string()).collect();
+        let literal = fq12_to_scrypt_string(&limbs);
+
+        // `FQ12 { x: c1, y: c0 }`, `FQ6 { x: c2, y: c1, z: c0 }` and `FQ2 { x: c1, y: c0 }`
+        let values: Vec<String> = Regex::new(r"(\d+)n")
+            .unwrap()
+            .captures_iter(&literal)
+            .map(|c| c[1].to_string())
+            .collect();
+        assert_eq!(values, limbs.into_iter().rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn pair_check_of_any_size() {
+        let three = scrypt_pair_check_bn128(
+            "vk.millerb1a1",
+            &[("a0", "proof.b"), ("vk_x", "vk.gamma"), ("proof.c", "vk.delta")],
+            "",
+        );
+        assert!(three.starts_with("return BN256Pairing.pairCheckP4Precalc(\n            a0,"));
+        assert!(three.contains("            vk.millerb1a1,\n            vk_x,"));
+
+        let four = scrypt_pair_check_bn128(
+            "vk.millerb1a1",
+            &[("a0", "b0"), ("a1", "b1"), ("a2", "b2"), ("a3", "b3")],
+            "check && ",
+        );
+        assert!(four.starts_with("let acc = vk.millerb1a1\n"));
+        assert_eq!(four.matches("BN256Pairing.millerAccumulate(acc, ").count(), 4);
+        assert!(four.contains("acc = BN256Pairing.millerAccumulate(acc, a3, b3)\n"));
+        assert!(four.ends_with("return check && BN256Pairing.pairCheckFinal(acc)"));
+
+        let lib = scrypt_pairing_lib_bn128();
+        assert!(lib.contains("static millerAccumulate(acc: FQ12, a: G1Point, b: G2Point): FQ12"));
+        assert!(lib.contains("static pairCheckFinal(acc: FQ12): boolean"));
+    }
+
+    fn egcd_steps(x: &BigUint, m: &BigUint) -> usize {
+        let (mut r, mut new_r) = (m.clone(), x.clone());
+        let mut steps = 0;
+        while !new_r.is_zero() {
+            let rem = &r % &new_r;
+            r = new_r;
+            new_r = rem;
+            steps += 1;
+        }
+        steps
+    }
+
+    #[test]
+    fn egcd_bound_matches_embedded_libraries() {
+        let bn128_p = BigUint::from_str_radix(
+            "21888242871839275222246405745257275088696311157297823662689037894645226208583",
+            10,
+        )
+        .unwrap();
+        let bls12_381_p = BigUint::from_str_radix("1a0111ea397fe69a4b1ba7b6434bacd764774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab", 16).unwrap();
+
+        assert_eq!(egcd_iteration_bound(&bn128_p), 364);
+        assert_eq!(egcd_iteration_bound(&bls12_381_p), 548);
+    }
+
+    #[test]
+    fn secp256k1_egcd_bound_is_sufficient() {
+        let n = BigUint::from_str_radix(SECP256K1_ORDER, 10).unwrap();
+        assert_eq!(egcd_iteration_bound(&n), SECP256K1_UB);
+
+        // the bound is attained modulo `F(k)`, the largest Fibonacci number not exceeding the
+        // order, by the input `F(k - 1)`
+        let (mut previous, mut current) = (BigUint::zero(), BigUint::one());
+        while &previous + &current <= n {
+            let next = &previous + &current;
+            previous = current;
+            current = next;
+        }
+        assert_eq!(egcd_steps(&previous, &current), SECP256K1_UB);
+
+        let inputs = vec![
+            previous,
+            &n - 1u32,
+            BigUint::from(2u32),
+            BigUint::from_str_radix("deadbeef", 16).unwrap(),
+        ];
+        for x in inputs {
+            assert!(egcd_steps(&x, &n) <= SECP256K1_UB);
+        }
+    }
+
+    fn function_body<'a>(lib: &'a str, name: &str) -> &'a str {
+        let start = lib.find(&format!("static function {}(", name)).unwrap();
+        let end = start + lib[start..].find("\n    }\n").unwrap();
+        &lib[start..end]
+    }
+
+    #[test]
+    fn fused_miller_matches_miller() {
+        let lib = scrypt_pairing_lib_bn128();
+        let miller = miller_body_bn128(lib);
+
+        // a single pair goes through the same steps as `miller`
+        let single = fused_miller_bn128(miller, 1).replace("_0", "");
+        assert_eq!(
+            single,
+            miller.replace(
+                "return BN256.modFQ12(BN256Pairing.mulLine(ret, lfr.a, lfr.b, lfr.c))",
+                "ret = BN256Pairing.mulLine(ret, lfr.a, lfr.b, lfr.c)\n        return BN256.modFQ12(ret)"
+            )
+        );
+
+        // three pairs share the squarings and reductions of the accumulator
+        let fused = &lib[lib.find("static millerFused3(").unwrap()..];
+        let fused = &fused[..fused.find("\n    }\n").unwrap()];
+        assert!(fused.starts_with(
+            "static millerFused3(q_0: TwistPoint, p_0: CurvePoint, q_1: TwistPoint, p_1: CurvePoint, q_2: TwistPoint, p_2: CurvePoint): FQ12 {"
+        ));
+        for (line, count) in [
+            ("ret = BN256.squareFQ12(ret)", 1),
+            ("ret = BN256.modFQ12(ret)", 1),
+            ("BN256Pairing.mulLine(ret, lfr", 3),
+            ("BN256Pairing.lineFuncDouble(r", 3),
+            ("BN256Pairing.lineFuncAdd(r", 3),
+        ] {
+            assert_eq!(fused.matches(line).count(), count * miller.matches(line).count());
+        }
+        assert!(fused.contains("const bAffine_2 = BN256.makeAffineCurvePoint(p_2)"));
+        assert!(!fused.contains("<%"));
+        assert!(!Regex::new(r"\blfr\b").unwrap().is_match(fused));
+    }
+
+    #[test]
+    fn pairing_libs_are_cached() {
+        assert_eq!(scrypt_pairing_lib_bn128(), build_scrypt_pairing_lib_bn128());
+        assert_eq!(
+            scrypt_pairing_lib_bls12_381(),
+            build_scrypt_pairing_lib_bls12_381()
+        );
+        assert!(std::ptr::eq(
+            scrypt_pairing_lib_bn128(),
+            scrypt_pairing_lib_bn128()
+        ));
+    }
+
+    #[test]
+    fn join_normalizes_line_endings() {
+        let sections = ScryptVerifierSections {
+            pairing_lib: "import {}\r\n\r\n".into(),
+            verifying_key_data: "const a = 1\r".to_string(),
+            snark_class: "class B {}\n\n\n".to_string(),
+            ..ScryptVerifierSections::default()
+        };
+        assert_eq!(sections.join(), "import {}\n\nconst a = 1\nclass B {}\n");
+    }
+
+    #[test]
+    fn write_sections_one_at_a_time() {
+        let sections = |pairing_lib: &str, verifying_key_data: &str, snark_class: &str| {
+            ScryptVerifierSections {
+                pairing_lib: pairing_lib.to_string().into(),
+                verifying_key_data: verifying_key_data.to_string(),
+                snark_class: snark_class.to_string(),
+                ..ScryptVerifierSections::default()
+            }
+        };
+
+        // a CR and a LF in consecutive sections form a single line ending
+        assert_eq!(sections("a\r", "\nb", "").join(), "a\nb\n");
+        assert_eq!(sections("a\r", "", "\nb").join(), "a\nb\n");
+        // trailing newlines of earlier sections are kept only if text follows
+        assert_eq!(sections("a\n\n", "\n", "").join(), "a\n");
+        assert_eq!(sections("a\n", "\r\n", "b").join(), "a\n\nb\n");
+        assert_eq!(sections("", "", "").join(), "\n");
+    }
+
+    #[test]
+    fn bls12_381_miller_loop_matches_x() {
+        let lib = scrypt_pairing_lib_bls12_381();
+        let steps = miller_loop_steps(BLS12_381_X);
+        let doublings = steps.matches('D').count();
+        assert_eq!(steps.len(), 68);
+        assert_eq!(doublings, 63);
+
+        let pre_compute = function_body(&lib, "preCompute");
+        assert!(pre_compute.contains(&format!("fe6[{}] ellCoeffs", steps.len())));
+        let unrolled: String = Regex::new(r"(doublingStep|additionStep)\(")
+            .unwrap()
+            .captures_iter(pre_compute)
+            .map(|c| if &c[1] == "doublingStep" { 'D' } else { 'A' })
+            .collect();
+        assert_eq!(unrolled, steps);
+
+        // one line evaluation per step, one squaring per doubling but the first
+        let miller_loop = function_body(&lib, "millerLoop");
+        assert_eq!(miller_loop.matches("mulBy014AssignFe12(").count(), steps.len());
+        assert_eq!(miller_loop.matches("squareFe12(").count(), doublings - 1);
+    }
+}
@@ -1,13 +1,87 @@
 use crate::Scheme;
 use serde::{de::DeserializeOwned, Serialize};
-use zokrates_field::{Bn128Field, Field};
+use std::convert::TryFrom;
+use zokrates_field::{Bls12_381Field, Bn128Field, Field};
 
 pub trait SolidityCompatibleField: Field {}
 impl SolidityCompatibleField for Bn128Field {}
+impl SolidityCompatibleField for Bls12_381Field {}
 pub trait SolidityCompatibleScheme<T: SolidityCompatibleField>: Scheme<T> {
     type Proof: From<Self::ProofPoints> + Serialize + DeserializeOwned + Clone;
 
-    fn export_solidity_verifier(vk: Self::VerificationKey) -> String;
+    fn export_solidity_verifier(
+        vk: Self::VerificationKey,
+        config: &SolidityExportConfig,
+    ) -> Result<String, String>;
+}
+
+/// Interface of the BN254 precompiles called by the `Pairing` library
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precompile {
+    /// `ecAdd`, `ecMul` and `ecPairing` at addresses 6, 7 and 8, as specified by EIP-196 and EIP-197
+    Eip197,
+    /// The EIP-196/EIP-197 interface deployed at other addresses, as found on some L2s
+    Custom { add: u64, mul: u64, pairing: u64 },
+}
+
+impl Default for Precompile {
+    fn default() -> Self {
+        Precompile::Eip197
+    }
+}
+
+impl Precompile {
+    /// Addresses of `ecAdd`, `ecMul` and `ecPairing`
+    pub fn addresses(&self) -> (u64, u64, u64) {
+        match *self {
+            Precompile::Eip197 => (6, 7, 8),
+            Precompile::Custom { add, mul, pairing } => (add, mul, pairing),
+        }
+    }
+}
+
+fn parse_address(s: &str) -> Result<u64, String> {
+    match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(hex) => u64::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(|_| format!("Invalid precompile address `{}`", s))
+}
+
+impl TryFrom<&str> for Precompile {
+    type Error = String;
+
+    fn try_from(s: &str) -> Result<Precompile, Self::Error> {
+        match s {
+            "eip197" => Ok(Precompile::Eip197),
+            s => match s.strip_prefix("custom:") {
+                Some(addresses) => match addresses.split(',').collect::<Vec<_>>()[..] {
+                    [add, mul, pairing] => Ok(Precompile::Custom {
+                        add: parse_address(add)?,
+                        mul: parse_address(mul)?,
+                        pairing: parse_address(pairing)?,
+                    }),
+                    _ => Err(format!(
+                        "Expected three precompile addresses `custom:<add>,<mul>,<pairing>`, found `{}`",
+                        s
+                    )),
+                },
+                None => Err(format!("Unknown precompile variant `{}`", s)),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SolidityExportConfig {
+    pub precompile: Precompile,
+}
+
+impl SolidityExportConfig {
+    pub fn precompile(mut self, precompile: Precompile) -> Self {
+        self.precompile = precompile;
+        self
+    }
 }
 
 pub const SOLIDITY_G2_ADDITION_LIB: &str = r#"// SPDX-License-Identifier: LGPL-3.0-only
@@ -408,7 +482,7 @@ library BN256G2 {
 }
 "#;
 
-pub fn solidity_pairing_lib(with_g2_addition: bool) -> String {
+pub fn solidity_pairing_lib(with_g2_addition: bool, precompile: &Precompile) -> String {
     let pairing_lib_beginning = r#"// This file is MIT Licensed.
 //
 // Copyright 2017 Christian Reitwiessner
@@ -456,7 +530,7 @@ library Pairing {
         input[3] = p2.Y;
         bool success;
         assembly {
-            success := staticcall(sub(gas(), 2000), 6, input, 0xc0, r, 0x60)
+            success := staticcall(sub(gas(), 2000), <%precompile_add%>, input, 0xc0, r, 0x60)
             // Use "invalid" to make gas estimation work
             switch success case 0 { invalid() }
         }
@@ -481,7 +555,7 @@ library Pairing {
         input[2] = s;
         bool success;
         assembly {
-            success := staticcall(sub(gas(), 2000), 7, input, 0x80, r, 0x60)
+            success := staticcall(sub(gas(), 2000), <%precompile_mul%>, input, 0x80, r, 0x60)
             // Use "invalid" to make gas estimation work
             switch success case 0 { invalid() }
         }
@@ -508,7 +582,7 @@ library Pairing {
         uint[1] memory out;
         bool success;
         assembly {
-            success := staticcall(sub(gas(), 2000), 8, add(input, 0x20), mul(inputSize, 0x20), out, 0x20)
+            success := staticcall(sub(gas(), 2000), <%precompile_pairing%>, add(input, 0x20), mul(inputSize, 0x20), out, 0x20)
             // Use "invalid" to make gas estimation work
             switch success case 0 { invalid() }
         }
@@ -563,7 +637,7 @@ library Pairing {
 }
 "#;
 
-    if !with_g2_addition {
+    let pairing_lib = if !with_g2_addition {
         [pairing_lib_beginning, pairing_lib_ending].join("\n")
     } else {
         [
@@ -572,5 +646,128 @@ library Pairing {
             pairing_lib_ending,
         ]
         .join("\n")
+    };
+
+    let (add, mul, pairing) = precompile.addresses();
+    pairing_lib
+        .replace("<%precompile_add%>", &add.to_string())
+        .replace("<%precompile_mul%>", &mul.to_string())
+        .replace("<%precompile_pairing%>", &pairing.to_string())
+}
+
+/// `Pairing` library over BLS12-381, calling the EIP-2537 precompiles `BLS12_G1ADD`,
+/// `BLS12_G1MSM` and `BLS12_PAIRING_CHECK` at their fixed addresses. Base field elements
+/// are split into their 16 most and 32 least significant bytes, which ABI encodes them
+/// as the 64 byte big-endian words the precompiles take.
+pub fn solidity_pairing_lib_bls12_381() -> String {
+    r#"// SPDX-License-Identifier: LGPL-3.0-only
+pragma solidity ^0.8.4;
+library Pairing {
+    // The prime q of the base field, split as (Q_A << 256) + Q_B
+    uint constant Q_A = 0x1a0111ea397fe69a4b1ba7b6434bacd7;
+    uint constant Q_B = 0x64774b84f38512bf6730d2a0f6b0f6241eabfffeb153ffffb9feffffffffaaab;
+    // Encoding of field elements is: a * 2^256 + b
+    struct Fp {
+        uint a;
+        uint b;
+    }
+    // Encoding of field elements is: c0 + c1 * u
+    struct Fp2 {
+        Fp c0;
+        Fp c1;
+    }
+    struct G1Point {
+        Fp X;
+        Fp Y;
+    }
+    struct G2Point {
+        Fp2 X;
+        Fp2 Y;
+    }
+    /// @return the negation of p, i.e. p.addition(p.negate()) should be zero.
+    function negate(G1Point memory p) pure internal returns (G1Point memory) {
+        if (p.Y.a == 0 && p.Y.b == 0)
+            return p;
+        uint a = Q_A - p.Y.a;
+        uint b;
+        unchecked { b = Q_B - p.Y.b; }
+        if (p.Y.b > Q_B)
+            a -= 1;
+        return G1Point(p.X, Fp(a, b));
+    }
+    /// @return r the sum of two points of G1
+    function addition(G1Point memory p1, G1Point memory p2) internal view returns (G1Point memory r) {
+        (bool success, bytes memory output) = address(0x0b).staticcall(abi.encode(p1, p2));
+        require(success && output.length == 128);
+        r = abi.decode(output, (G1Point));
+    }
+    /// @return r the product of a point on G1 and a scalar, i.e.
+    /// p == p.scalar_mul(1) and p.addition(p) == p.scalar_mul(2) for all points p.
+    function scalar_mul(G1Point memory p, uint s) internal view returns (G1Point memory r) {
+        (bool success, bytes memory output) = address(0x0c).staticcall(abi.encode(p, s));
+        require(success && output.length == 128);
+        r = abi.decode(output, (G1Point));
+    }
+    /// @return the result of computing the pairing check
+    /// e(p1[0], p2[0]) *  .... * e(p1[n], p2[n]) == 1
+    function pairing(G1Point[] memory p1, G2Point[] memory p2) internal view returns (bool) {
+        require(p1.length == p2.length);
+        bytes memory input;
+        for (uint i = 0; i < p1.length; i++)
+        {
+            input = bytes.concat(input, abi.encode(p1[i], p2[i]));
+        }
+        (bool success, bytes memory output) = address(0x0f).staticcall(input);
+        require(success && output.length == 32);
+        return abi.decode(output, (uint)) == 1;
+    }
+    /// Convenience method for a pairing check for four pairs.
+    function pairingProd4(
+            G1Point memory a1, G2Point memory a2,
+            G1Point memory b1, G2Point memory b2,
+            G1Point memory c1, G2Point memory c2,
+            G1Point memory d1, G2Point memory d2
+    ) internal view returns (bool) {
+        G1Point[] memory p1 = new G1Point[](4);
+        G2Point[] memory p2 = new G2Point[](4);
+        p1[0] = a1;
+        p1[1] = b1;
+        p1[2] = c1;
+        p1[3] = d1;
+        p2[0] = a2;
+        p2[1] = b2;
+        p2[2] = c2;
+        p2[3] = d2;
+        return pairing(p1, p2);
+    }
+}
+"#
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn precompile_variant_changes_invocation() {
+        let standard = solidity_pairing_lib(false, &Precompile::default());
+        assert!(standard.contains("staticcall(sub(gas(), 2000), 6, input, 0xc0, r, 0x60)"));
+        assert!(standard.contains("staticcall(sub(gas(), 2000), 7, input, 0x80, r, 0x60)"));
+        assert!(standard.contains("staticcall(sub(gas(), 2000), 8, add(input, 0x20)"));
+
+        let precompile = Precompile::try_from("custom:0x106,263,0x108").unwrap();
+        let custom = solidity_pairing_lib(true, &precompile);
+        assert!(custom.contains("staticcall(sub(gas(), 2000), 262, input, 0xc0, r, 0x60)"));
+        assert!(custom.contains("staticcall(sub(gas(), 2000), 263, input, 0x80, r, 0x60)"));
+        assert!(custom.contains("staticcall(sub(gas(), 2000), 264, add(input, 0x20)"));
+        assert!(!custom.contains("<%precompile_"));
+    }
+
+    #[test]
+    fn invalid_precompile_variants_are_rejected() {
+        assert!(Precompile::try_from("eip2537").is_err());
+        assert!(Precompile::try_from("custom:6,7").is_err());
+        assert!(Precompile::try_from("custom:6,7,0xzz").is_err());
     }
 }
@@ -1,14 +1,17 @@
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use zokrates_common::helpers::{CurveParameter, SchemeParameter};
 use zokrates_field::Field;
 
 use super::{Fr, Scheme, SetupKeypair};
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
+#[serde(bound(deserialize = ""))]
 pub struct TaggedVerificationKey<T: Field, S: Scheme<T>> {
     scheme: String,
     curve: String,
     #[serde(flatten)]
-    vk: S::VerificationKey,
+    pub vk: S::VerificationKey,
 }
 
 #[derive(Serialize)]
@@ -44,6 +47,23 @@ impl<T: Field, S: Scheme<T>> TaggedVerificationKey<T, S> {
             vk,
         }
     }
+
+    /// Checks that the key is tagged with the curve of `T` and the scheme `S`, so that a
+    /// key read with other parameters than its own is rejected
+    pub fn check_tags(&self) -> Result<(), String> {
+        let curve = CurveParameter::try_from(self.curve.as_str())?;
+        let scheme = SchemeParameter::try_from(self.scheme.as_str())?;
+        let expected_curve = CurveParameter::try_from(T::name())?;
+        let expected_scheme = SchemeParameter::try_from(S::NAME)?;
+
+        if curve != expected_curve || scheme != expected_scheme {
+            return Err(format!(
+                "Verification key is tagged with curve {} and scheme {}, expected curve {} and scheme {}",
+                curve, scheme, expected_curve, expected_scheme
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl<T: Field, S: Scheme<T>> TaggedKeypair<T, S> {